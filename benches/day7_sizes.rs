@@ -0,0 +1,75 @@
+//! Times parsing a 2022 day 7 transcript and answering part 1 from it, reading the directory
+//! sizes cached while the tree is built against summing every subtree again for each directory,
+//! on the real input and on deep generated transcripts.
+//!
+//! Run with `cargo bench --bench day7_sizes`.
+
+use advent_of_code::{
+    bench::{speedup_header, speedup_row, time},
+    year2022::day7::{Directory, Tree, parse_tree},
+};
+
+/// A session that goes `depth` directories deep, listing `width` files and one subdirectory at
+/// each level.
+fn synthetic_transcript(depth: usize, width: usize) -> String {
+    let mut transcript = String::from("$ cd /\n");
+    for level in 0..depth {
+        transcript.push_str("$ ls\n");
+        for file in 0..width {
+            transcript.push_str(&format!(
+                "{} f{file}.txt\n",
+                (level * 7919 + file * 104729) % 100000
+            ));
+        }
+        transcript.push_str(&format!("dir d{level}\n$ cd d{level}\n"));
+    }
+    transcript
+}
+
+/// The total size summed from every file below, as before sizes were cached.
+fn recomputed_size(dir: &Directory) -> usize {
+    dir.children()
+        .iter()
+        .map(|c| match c {
+            Tree::File(f) => f.size(),
+            Tree::Directory(d) => recomputed_size(d),
+        })
+        .sum()
+}
+
+/// Part 1 on `input`: the sum of the directories of at most 100000, with sizes found by `size`.
+fn small_total(input: &str, size: impl Fn(&Directory) -> usize) -> usize {
+    parse_tree(input)
+        .unwrap()
+        .descendants_and_self()
+        .map(size)
+        .filter(|&s| s <= 100000)
+        .sum()
+}
+
+fn compare(name: &str, input: &str, iterations: usize) {
+    assert_eq!(
+        small_total(input, recomputed_size),
+        small_total(input, Directory::size),
+        "sizes disagree on {name}"
+    );
+
+    let recomputed = time(iterations, || small_total(input, recomputed_size));
+    let cached = time(iterations, || small_total(input, Directory::size));
+    println!("{}", speedup_row(name, &recomputed, &cached));
+}
+
+fn main() {
+    println!("{}", speedup_header("Transcript", "Recomputed", "Cached"));
+    match std::fs::read_to_string("input/2022/day7.txt") {
+        Ok(input) => compare("input/2022/day7.txt", &input, 200),
+        Err(e) => eprintln!("skipping input/2022/day7.txt: {e}"),
+    }
+    for (depth, iterations) in [(100, 50), (500, 10), (2000, 5), (8000, 3)] {
+        compare(
+            &format!("synthetic depth {depth}"),
+            &synthetic_transcript(depth, 4),
+            iterations,
+        );
+    }
+}
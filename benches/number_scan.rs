@@ -0,0 +1,218 @@
+//! Compares the parsers ported to `util::scan` with the ones they replaced, on the real inputs:
+//! the regexes of 2022 days 11 and 14, the digit filtering of day 5 and the `atoi` calls of
+//! day 13.
+//!
+//! Run with `cargo bench --bench number_scan`.
+
+use std::collections::VecDeque;
+
+use advent_of_code::{
+    bench::{speedup_header, speedup_row, time},
+    year2022::{
+        day5::{self, Instruction},
+        day11, day13,
+        day14::Cave,
+    },
+};
+use itertools::Itertools;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// 2022 day 5's instructions, keeping only digits and spaces and splitting on the spaces.
+fn day5_filtered(input: &str) -> Vec<Instruction> {
+    input
+        .lines()
+        .skip_while(|l| l.is_empty())
+        .skip_while(|l| !l.is_empty())
+        .filter(|l| !l.is_empty())
+        .map(|l| {
+            l.chars()
+                .filter(|c| c.is_ascii_digit() || c.is_whitespace())
+                .collect::<String>()
+        })
+        .map(|s| {
+            let mut numbers = s.split_ascii_whitespace().map(|n| n.parse().unwrap());
+            Instruction::new(
+                numbers.next().unwrap(),
+                numbers.next().unwrap(),
+                numbers.next().unwrap(),
+            )
+        })
+        .collect()
+}
+
+// Named and laid out like day 11's own types, so that both parsers' monkeys debug-print the same.
+#[derive(Debug)]
+enum Operator {
+    Add,
+    Multiply,
+}
+
+#[derive(Debug)]
+#[allow(dead_code)]
+struct Monkey {
+    items: VecDeque<u64>,
+    operator: Operator,
+    operand_left: Option<u64>,
+    operand_right: Option<u64>,
+    test_divisor: u64,
+    true_monkey: usize,
+    false_monkey: usize,
+    num_inspects: usize,
+}
+
+static MONKEY_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?x-u)\s*
+Monkey \s (?P<index>\d+):\s*
+Starting \s items: \s (?P<items>[\d,\s]+)\s*
+Operation: \s new \s = \s (?P<oper_left>old|\d+)\s(?P<operator>[\*\+])\s(?P<oper_right>old|\d+)\s*
+Test: \s divisible \s by \s (?P<test_divisor>\d+)\s*
+If \s true: \s throw \s to \s monkey \s (?P<true_monkey>\d+)\s*
+If \s false: \s throw \s to \s monkey \s (?P<false_monkey>\d+)\s*
+",
+    )
+    .unwrap()
+});
+static ITEM_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?-u)(\d+)[, ]*").unwrap());
+
+/// 2022 day 11's monkeys, matched by `MONKEY_REGEX` with their items split by `ITEM_REGEX`.
+fn day11_regex(input: &str) -> Vec<Monkey> {
+    MONKEY_REGEX
+        .captures_iter(input)
+        .map(|c| Monkey {
+            items: ITEM_REGEX
+                .captures_iter(&c["items"])
+                .filter_map(|i| i[1].parse().ok())
+                .collect(),
+            operator: match &c["operator"] {
+                "*" => Operator::Multiply,
+                _ => Operator::Add,
+            },
+            operand_left: c["oper_left"].parse().ok(),
+            operand_right: c["oper_right"].parse().ok(),
+            test_divisor: c["test_divisor"].parse().unwrap(),
+            true_monkey: c["true_monkey"].parse().unwrap(),
+            false_monkey: c["false_monkey"].parse().unwrap(),
+            num_inspects: 0,
+        })
+        .collect()
+}
+
+/// A 2022 day 13 packet, split at its top-level commas with integers read by `atoi`.
+fn day13_value(bytes: &[u8]) -> Option<day13::Value> {
+    if bytes.first() != Some(&b'[') {
+        return atoi::atoi(bytes).map(day13::Value::Integer);
+    }
+    let mut unclosed = 0;
+    let mut indices = VecDeque::new();
+    let (closing, _) = bytes.iter().enumerate().find(|&(i, &c)| {
+        match c {
+            b'[' => unclosed += 1,
+            b']' => unclosed -= 1,
+            b',' if unclosed == 1 => indices.push_back(i),
+            _ => {}
+        }
+        unclosed == 0
+    })?;
+    indices.push_back(closing);
+    let mut values = Vec::new();
+    let mut left = 1;
+    while let Some(right) = indices.pop_front() {
+        values.extend(day13_value(&bytes[left..closing]));
+        left = right + 1;
+    }
+    Some(day13::Value::List(values))
+}
+
+fn day13_atoi(input: &str) -> Vec<day13::Value> {
+    input
+        .lines()
+        .filter_map(|l| day13_value(l.as_bytes()))
+        .collect()
+}
+
+static ROCK_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?-u)(\d+),(\d+)").unwrap());
+
+/// 2022 day 14's rock, drawn between the points `ROCK_REGEX` finds on each line, as the cave's
+/// row-major cells.
+fn day14_regex(input: &str) -> Vec<bool> {
+    let (width, height) = (1000, 200);
+    let mut cells = vec![false; width * height];
+    for line in input.lines() {
+        let points = ROCK_REGEX.captures_iter(line).filter_map(|c| {
+            c[1].parse()
+                .and_then(|x: usize| c[2].parse().map(|y: usize| (x, y)))
+                .ok()
+        });
+        for (from, to) in points.tuple_windows() {
+            for x in from.0.min(to.0)..=from.0.max(to.0) {
+                for y in from.1.min(to.1)..=from.1.max(to.1) {
+                    cells[y * width + x] = true;
+                }
+            }
+        }
+    }
+    cells
+}
+
+fn compare<A, B>(
+    name: &str,
+    iterations: usize,
+    old: impl Fn() -> A,
+    new: impl Fn() -> B,
+    same: impl Fn(&A, &B) -> bool,
+) {
+    assert!(same(&old(), &new()), "parsers disagree on {name}");
+    let old_stats = time(iterations, old);
+    let new_stats = time(iterations, new);
+    println!("{}", speedup_row(name, &old_stats, &new_stats));
+}
+
+fn read(path: &str) -> Option<String> {
+    std::fs::read_to_string(path)
+        .inspect_err(|e| eprintln!("skipping {path}: {e}"))
+        .ok()
+}
+
+fn main() {
+    println!("{}", speedup_header("Input", "Before", "Scan"));
+    if let Some(input) = read("input/2022/day5.txt") {
+        let crates = day5::parse_crates(&input).unwrap();
+        let heights: Vec<_> = crates.iter().map(Vec::len).collect();
+        compare(
+            "2022 day 5 digit filtering",
+            200,
+            || day5_filtered(&input),
+            || day5::parse_instructions(&input, &heights).unwrap(),
+            |a, b| a == b,
+        );
+    }
+    if let Some(input) = read("input/2022/day11.txt") {
+        compare(
+            "2022 day 11 regex",
+            200,
+            || day11_regex(&input),
+            || day11::parse_monkeys(&input).unwrap(),
+            |a, b| format!("{a:?}") == format!("{b:?}"),
+        );
+    }
+    if let Some(input) = read("input/2022/day13.txt") {
+        compare(
+            "2022 day 13 atoi",
+            200,
+            || day13_atoi(&input),
+            || day13::parse_lines(&input).unwrap(),
+            |a, b| a == b,
+        );
+    }
+    if let Some(input) = read("input/2022/day14.txt") {
+        compare(
+            "2022 day 14 regex",
+            200,
+            || day14_regex(&input),
+            || input.parse::<Cave>().unwrap(),
+            |a, b| a[..] == b.iter().copied().collect::<Vec<_>>()[..],
+        );
+    }
+}
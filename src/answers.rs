@@ -0,0 +1,365 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize, ser::SerializeMap};
+
+use crate::solution::{Answer, Part};
+
+/// An answer as written in `answers.toml`. Integers too large for TOML are kept as text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RecordedAnswer {
+    Integer(i64),
+    Text(String),
+}
+
+impl From<&Answer> for RecordedAnswer {
+    fn from(answer: &Answer) -> Self {
+        match answer {
+            Answer::Integer(i) => match i64::try_from(*i) {
+                Ok(i) => RecordedAnswer::Integer(i),
+                Err(_) => RecordedAnswer::Text(i.to_string()),
+            },
+            Answer::Text(s) | Answer::Rendering(s) => RecordedAnswer::Text(s.clone()),
+        }
+    }
+}
+
+impl Display for RecordedAnswer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecordedAnswer::Integer(i) => write!(f, "{i}"),
+            RecordedAnswer::Text(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+/// Where a recorded answer came from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Source {
+    /// Saved from a computed answer, as by `verify --record`, without asking the server
+    #[default]
+    Local,
+    /// Accepted by the server when it was submitted
+    Server,
+}
+
+impl Source {
+    pub fn is_local(&self) -> bool {
+        *self == Source::Local
+    }
+}
+
+/// Answers the server turned down for one part, so they are not submitted again.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rejected {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub wrong: Vec<RecordedAnswer>,
+    /// The largest answer that was too low
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub too_low: Option<i64>,
+    /// The smallest answer that was too high
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub too_high: Option<i64>,
+}
+
+impl Rejected {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayAnswers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part1: Option<RecordedAnswer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part2: Option<RecordedAnswer>,
+    #[serde(default, skip_serializing_if = "Source::is_local")]
+    pub part1_source: Source,
+    #[serde(default, skip_serializing_if = "Source::is_local")]
+    pub part2_source: Source,
+    #[serde(default, skip_serializing_if = "Rejected::is_empty")]
+    pub part1_rejected: Rejected,
+    #[serde(default, skip_serializing_if = "Rejected::is_empty")]
+    pub part2_rejected: Rejected,
+}
+
+impl DayAnswers {
+    pub fn get(&self, part: Part) -> Option<&RecordedAnswer> {
+        match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+        }
+    }
+
+    pub fn get_mut(&mut self, part: Part) -> &mut Option<RecordedAnswer> {
+        match part {
+            Part::One => &mut self.part1,
+            Part::Two => &mut self.part2,
+        }
+    }
+
+    pub fn source(&self, part: Part) -> Source {
+        match part {
+            Part::One => self.part1_source,
+            Part::Two => self.part2_source,
+        }
+    }
+
+    pub fn source_mut(&mut self, part: Part) -> &mut Source {
+        match part {
+            Part::One => &mut self.part1_source,
+            Part::Two => &mut self.part2_source,
+        }
+    }
+
+    pub fn rejected(&self, part: Part) -> &Rejected {
+        match part {
+            Part::One => &self.part1_rejected,
+            Part::Two => &self.part2_rejected,
+        }
+    }
+
+    pub fn rejected_mut(&mut self, part: Part) -> &mut Rejected {
+        match part {
+            Part::One => &mut self.part1_rejected,
+            Part::Two => &mut self.part2_rejected,
+        }
+    }
+}
+
+/// The known answers for one year, stored in `input/<year>/answers.toml` as one `[dayN]` table per day.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    days: BTreeMap<u8, DayAnswers>,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Io(PathBuf, std::io::Error),
+    Parse(PathBuf, toml::de::Error),
+    Serialize(toml::ser::Error),
+    BadKey(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io(path, e) => write!(f, "{}: {e}", path.display()),
+            Error::Parse(path, e) => write!(f, "{}: {e}", path.display()),
+            Error::Serialize(e) => write!(f, "{e}"),
+            Error::BadKey(key) => write!(f, "expected a table named dayN, found {key:?}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Answers {
+    pub fn path(year: u16) -> PathBuf {
+        PathBuf::from(format!("input/{year}/answers.toml"))
+    }
+
+    /// Loads a year's answers. A missing file is treated as no recorded answers.
+    pub fn load(year: u16) -> Result<Self, Error> {
+        Self::load_from(&Self::path(year))
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self, Error> {
+        match std::fs::read_to_string(path) {
+            Ok(s) => Self::from_toml(&s).map_err(|e| match e {
+                Error::Parse(_, e) => Error::Parse(path.to_path_buf(), e),
+                e => e,
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(Error::Io(path.to_path_buf(), e)),
+        }
+    }
+
+    pub fn save(&self, year: u16) -> Result<(), Error> {
+        self.save_to(&Self::path(year))
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<(), Error> {
+        std::fs::write(path, self.to_toml()?).map_err(|e| Error::Io(path.to_path_buf(), e))
+    }
+
+    pub fn from_toml(s: &str) -> Result<Self, Error> {
+        let tables: BTreeMap<String, DayAnswers> =
+            toml::from_str(s).map_err(|e| Error::Parse(PathBuf::new(), e))?;
+        let days = tables
+            .into_iter()
+            .map(|(key, answers)| {
+                key.strip_prefix("day")
+                    .and_then(|d| d.parse().ok())
+                    .map(|day| (day, answers))
+                    .ok_or(Error::BadKey(key))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { days })
+    }
+
+    pub fn to_toml(&self) -> Result<String, Error> {
+        toml::to_string(self).map_err(Error::Serialize)
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&RecordedAnswer> {
+        self.days.get(&day).and_then(|d| d.get(part))
+    }
+
+    /// The recorded answer, if the server accepted it.
+    pub fn confirmed(&self, day: u8, part: Part) -> Option<&RecordedAnswer> {
+        let day = self.days.get(&day)?;
+        day.get(part).filter(|_| day.source(part) == Source::Server)
+    }
+
+    pub fn rejected(&self, day: u8, part: Part) -> Option<&Rejected> {
+        self.days.get(&day).map(|d| d.rejected(part))
+    }
+
+    pub fn day(&self, day: u8) -> Option<&DayAnswers> {
+        self.days.get(&day)
+    }
+
+    pub fn day_mut(&mut self, day: u8) -> &mut DayAnswers {
+        self.days.entry(day).or_default()
+    }
+
+    /// Saves a computed answer that the server has not confirmed.
+    pub fn record(&mut self, day: u8, part: Part, answer: &Answer) {
+        let day = self.day_mut(day);
+        *day.get_mut(part) = Some(answer.into());
+        *day.source_mut(part) = Source::Local;
+    }
+}
+
+impl Serialize for Answers {
+    // Written by hand so tables come out in numeric day order rather than string order
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.days.len()))?;
+        for (day, answers) in self.days.iter() {
+            map.serialize_entry(&format!("day{day}"), answers)?;
+        }
+        map.end()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    /// No answer has been recorded yet
+    Missing,
+    /// The computed answer differs from the recorded one
+    Changed {
+        expected: RecordedAnswer,
+    },
+    /// The solution panicked or its input could not be read
+    Failed(String),
+}
+
+impl Status {
+    pub fn check(recorded: Option<&RecordedAnswer>, actual: &Answer) -> Self {
+        match recorded {
+            None => Status::Missing,
+            Some(expected) if *expected == RecordedAnswer::from(actual) => Status::Pass,
+            Some(expected) => Status::Changed {
+                expected: expected.clone(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r#"[day2]
+part1 = 15
+part2 = 12
+
+[day10]
+part1 = 13140
+part2 = """
+##..
+..##"""
+"#;
+
+    #[test]
+    pub fn parse_and_lookup() {
+        let answers = Answers::from_toml(EXAMPLE).unwrap();
+        assert_eq!(
+            Some(&RecordedAnswer::Integer(15)),
+            answers.get(2, Part::One)
+        );
+        assert_eq!(
+            Some(&RecordedAnswer::Text("##..\n..##".to_string())),
+            answers.get(10, Part::Two)
+        );
+        assert_eq!(None, answers.get(3, Part::One));
+    }
+
+    #[test]
+    pub fn roundtrip_in_day_order() {
+        let mut answers = Answers::default();
+        answers.record(10, Part::One, &Answer::Integer(13140));
+        answers.record(2, Part::One, &Answer::Integer(15));
+        answers.record(10, Part::Two, &Answer::Rendering("##..\n..##".into()));
+
+        let toml = answers.to_toml().unwrap();
+        assert!(toml.find("[day2]").unwrap() < toml.find("[day10]").unwrap());
+        assert_eq!(answers, Answers::from_toml(&toml).unwrap());
+    }
+
+    #[test]
+    pub fn sources() {
+        let mut answers =
+            Answers::from_toml("[day1]\npart1 = 5\npart2 = 7\npart2_source = \"server\"\n")
+                .unwrap();
+        assert_eq!(Source::Local, answers.day(1).unwrap().source(Part::One));
+        assert_eq!(None, answers.confirmed(1, Part::One));
+        assert_eq!(
+            Some(&RecordedAnswer::Integer(7)),
+            answers.confirmed(1, Part::Two)
+        );
+
+        let toml = answers.to_toml().unwrap();
+        assert!(!toml.contains("part1_source"));
+        assert_eq!(answers, Answers::from_toml(&toml).unwrap());
+
+        answers.record(1, Part::Two, &Answer::Integer(8));
+        assert_eq!(None, answers.confirmed(1, Part::Two));
+    }
+
+    #[test]
+    pub fn bad_key() {
+        assert!(matches!(
+            Answers::from_toml("[dayX]\npart1 = 1"),
+            Err(Error::BadKey(_))
+        ));
+    }
+
+    #[test]
+    pub fn status() {
+        let recorded = RecordedAnswer::Integer(15);
+        assert_eq!(
+            Status::Pass,
+            Status::check(Some(&recorded), &15usize.into())
+        );
+        assert_eq!(
+            Status::Changed {
+                expected: recorded.clone()
+            },
+            Status::check(Some(&recorded), &16usize.into())
+        );
+        assert_eq!(Status::Missing, Status::check(None, &16usize.into()));
+        assert_eq!(
+            Status::Pass,
+            Status::check(Some(&RecordedAnswer::Text("CMZ".into())), &"CMZ".into())
+        );
+    }
+}
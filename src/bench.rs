@@ -0,0 +1,258 @@
+use std::{
+    fmt::Write,
+    time::{Duration, Instant},
+};
+
+use itertools::Itertools;
+use serde::Serialize;
+
+use crate::{registry::Entry, solution::Timing, util::ParseError};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Stats {
+    #[serde(rename = "min_ns", serialize_with = "as_nanos")]
+    pub min: Duration,
+    #[serde(rename = "median_ns", serialize_with = "as_nanos")]
+    pub median: Duration,
+    #[serde(rename = "max_ns", serialize_with = "as_nanos")]
+    pub max: Duration,
+}
+
+fn as_nanos<S: serde::Serializer>(d: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u128(d.as_nanos())
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort_unstable();
+        if samples.is_empty() {
+            return Self::default();
+        }
+        let mid = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[mid - 1] + samples[mid]) / 2
+        } else {
+            samples[mid]
+        };
+        Self {
+            min: samples[0],
+            median,
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+impl std::ops::Add for Stats {
+    type Output = Stats;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Stats {
+            min: self.min + rhs.min,
+            median: self.median + rhs.median,
+            max: self.max + rhs.max,
+        }
+    }
+}
+
+impl std::iter::Sum for Stats {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Stats::default(), |a, s| a + s)
+    }
+}
+
+/// Runs `f` `iterations` times, timing each run.
+pub fn time<T>(iterations: usize, mut f: impl FnMut() -> T) -> Stats {
+    Stats::from_samples(
+        (0..iterations)
+            .map(|_| {
+                let start = Instant::now();
+                std::hint::black_box(f());
+                start.elapsed()
+            })
+            .collect(),
+    )
+}
+
+/// The header of a Markdown table comparing the median times of `baseline` and `contender` on
+/// each of several `subject`s, with [`speedup_row`] giving the rows.
+pub fn speedup_header(subject: &str, baseline: &str, contender: &str) -> String {
+    format!(
+        "| {subject} | {baseline} | {contender} | Speedup |\n|{}|{}:|{}:|--------:|",
+        "-".repeat(subject.len() + 2),
+        "-".repeat(baseline.len() + 1),
+        "-".repeat(contender.len() + 1)
+    )
+}
+
+/// A row of a [`speedup_header`] table: both medians and how many times faster `contender` is.
+pub fn speedup_row(name: &str, baseline: &Stats, contender: &Stats) -> String {
+    let speedup =
+        baseline.median.as_secs_f64() / contender.median.max(Duration::from_nanos(1)).as_secs_f64();
+    format!(
+        "| {name} | {:.2?} | {:.2?} | {speedup:.1}× |",
+        baseline.median, contender.median
+    )
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DayBench {
+    pub year: u16,
+    pub day: u8,
+    pub iterations: usize,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+    /// Statistics of parse + part1 + part2 as measured together in each iteration
+    pub total: Stats,
+}
+
+impl DayBench {
+    pub fn from_timings(year: u16, day: u8, timings: &[Timing]) -> Self {
+        let stats =
+            |f: fn(&Timing) -> Duration| Stats::from_samples(timings.iter().map(f).collect());
+        Self {
+            year,
+            day,
+            iterations: timings.len(),
+            parse: stats(|t| t.parse),
+            part1: stats(|t| t.part1),
+            part2: stats(|t| t.part2),
+            total: stats(|t| t.parse + t.part1 + t.part2),
+        }
+    }
+}
+
+pub fn bench(entry: &Entry, input: &str, iterations: usize) -> Result<DayBench, ParseError> {
+    let timings: Vec<Timing> = (0..iterations.max(1))
+        .map(|_| entry.solution.solve_timed(input).map(|(_, timing)| timing))
+        .collect::<Result<_, _>>()?;
+    Ok(DayBench::from_timings(entry.year, entry.day, &timings))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct YearBench {
+    pub year: u16,
+    pub days: Vec<DayBench>,
+    pub total: Stats,
+}
+
+pub fn by_year(results: Vec<DayBench>) -> Vec<YearBench> {
+    results
+        .into_iter()
+        .group_by(|b| b.year)
+        .into_iter()
+        .map(|(year, days)| {
+            let days = days.collect_vec();
+            YearBench {
+                year,
+                total: days.iter().map(|d| d.total).sum(),
+                days,
+            }
+        })
+        .collect()
+}
+
+fn format_stats(stats: &Stats) -> String {
+    format!(
+        "{:.2?} ({:.2?} – {:.2?})",
+        stats.median, stats.min, stats.max
+    )
+}
+
+/// One table per year with the median (min – max) of each phase, followed by the year's total.
+pub fn to_markdown(years: &[YearBench]) -> String {
+    let mut out = String::new();
+    for year in years {
+        writeln!(out, "## {}\n", year.year).unwrap();
+        writeln!(out, "| Day | Parse | Part 1 | Part 2 | Total |").unwrap();
+        writeln!(out, "|----:|------:|-------:|-------:|------:|").unwrap();
+        for day in year.days.iter() {
+            writeln!(
+                out,
+                "| {} | {} | {} | {} | {} |",
+                day.day,
+                format_stats(&day.parse),
+                format_stats(&day.part1),
+                format_stats(&day.part2),
+                format_stats(&day.total)
+            )
+            .unwrap();
+        }
+        writeln!(out, "| **Total** | | | | {} |\n", format_stats(&year.total)).unwrap();
+    }
+    out
+}
+
+pub fn to_json(years: &[YearBench]) -> String {
+    serde_json::to_string_pretty(years).expect("bench results serialize")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    pub fn stats() {
+        let stats = Stats::from_samples(vec![ms(3), ms(1), ms(2)]);
+        assert_eq!(
+            Stats {
+                min: ms(1),
+                median: ms(2),
+                max: ms(3)
+            },
+            stats
+        );
+        assert_eq!(
+            ms(2),
+            Stats::from_samples(vec![ms(4), ms(1), ms(3), ms(1)]).median
+        );
+    }
+
+    #[test]
+    pub fn speedups() {
+        assert_eq!(
+            "| Map | All queued | BFS | Speedup |\n|-----|-----------:|----:|--------:|",
+            speedup_header("Map", "All queued", "BFS")
+        );
+        let stats = |n| Stats::from_samples(vec![ms(n)]);
+        assert_eq!(
+            "| input | 6.00ms | 2.00ms | 3.0× |",
+            speedup_row("input", &stats(6), &stats(2))
+        );
+
+        let mut runs = 0;
+        time(3, || runs += 1);
+        assert_eq!(3, runs);
+    }
+
+    #[test]
+    pub fn totals_per_year() {
+        let timing = |n| Timing {
+            parse: ms(n),
+            part1: ms(n),
+            part2: ms(n),
+        };
+        let results = vec![
+            DayBench::from_timings(2022, 1, &[timing(1), timing(3)]),
+            DayBench::from_timings(2022, 2, &[timing(2)]),
+            DayBench::from_timings(2025, 1, &[timing(5)]),
+        ];
+        let years = by_year(results);
+
+        assert_eq!(2, years.len());
+        assert_eq!(ms(6 + 6), years[0].total.median);
+        assert_eq!(ms(3 + 6), years[0].total.min);
+        assert_eq!(ms(15), years[1].total.max);
+
+        let markdown = to_markdown(&years);
+        assert!(markdown.contains("## 2022"));
+        assert!(markdown.contains("| 2 | 2.00ms (2.00ms – 2.00ms) |"));
+
+        let json: serde_json::Value = serde_json::from_str(&to_json(&years)).unwrap();
+        assert_eq!(2_000_000, json[0]["days"][1]["parse"]["median_ns"]);
+    }
+}
@@ -0,0 +1,167 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+/// Where the runner looks for its settings unless `AOC_CONFIG` names another file.
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+/// Settings for fetching inputs, read from `aoc.toml`. Relative paths are relative to the
+/// directory holding the file. For example:
+///
+/// ```toml
+/// cache_dir = "input"
+/// mirror = "../puzzles"                # or:
+/// url = "http://127.0.0.1:8000"
+/// submit_url = "http://127.0.0.1:8000" # defaults to `url`
+/// session_file = ".session"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Where inputs are kept as `<year>/day<N>.txt`
+    #[serde(default = "default_cache_dir")]
+    pub cache_dir: PathBuf,
+    /// A directory laid out like the cache to copy missing inputs from
+    pub mirror: Option<PathBuf>,
+    /// A server laid out like the puzzle site to download missing inputs from
+    pub url: Option<String>,
+    /// A server laid out like the puzzle site to post answers to, if not `url`
+    pub submit_url: Option<String>,
+    /// The session cookie to send to `url` and `submit_url`
+    pub session: Option<String>,
+    /// A file holding the session cookie, if `session` is not given
+    pub session_file: Option<PathBuf>,
+}
+
+fn default_cache_dir() -> PathBuf {
+    PathBuf::from("input")
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            cache_dir: default_cache_dir(),
+            mirror: None,
+            url: None,
+            submit_url: None,
+            session: None,
+            session_file: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Io(PathBuf, std::io::Error),
+    Parse(PathBuf, toml::de::Error),
+    /// Both `mirror` and `url` are set
+    TwoSources(PathBuf),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io(path, e) => write!(f, "{}: {e}", path.display()),
+            Error::Parse(path, e) => write!(f, "{}: {e}", path.display()),
+            Error::TwoSources(path) => {
+                write!(
+                    f,
+                    "{}: set either `mirror` or `url`, not both",
+                    path.display()
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Config {
+    /// Loads the file named by `AOC_CONFIG`, or `aoc.toml`, with `AOC_SESSION` overriding the
+    /// session. A missing file gives the defaults.
+    pub fn load() -> Result<Self, Error> {
+        let path = std::env::var_os("AOC_CONFIG").map_or_else(|| CONFIG_FILE.into(), PathBuf::from);
+        let mut config = Self::load_from(&path)?;
+        if let Ok(session) = std::env::var("AOC_SESSION") {
+            config.session = Some(session);
+        }
+        Ok(config)
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self, Error> {
+        let s = match std::fs::read_to_string(path) {
+            Ok(s) => s,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(Error::Io(path.to_path_buf(), e)),
+        };
+        let config: Self = toml::from_str(&s).map_err(|e| Error::Parse(path.to_path_buf(), e))?;
+        if config.mirror.is_some() && config.url.is_some() {
+            return Err(Error::TwoSources(path.to_path_buf()));
+        }
+        Ok(config.relative_to(path.parent().unwrap_or(Path::new(""))))
+    }
+
+    fn relative_to(self, base: &Path) -> Self {
+        Self {
+            cache_dir: base.join(self.cache_dir),
+            mirror: self.mirror.map(|m| base.join(m)),
+            session_file: self.session_file.map(|s| base.join(s)),
+            ..self
+        }
+    }
+
+    /// Where answers are posted: `submit_url`, or else `url`.
+    pub fn submit_url(&self) -> Option<&str> {
+        self.submit_url.as_deref().or(self.url.as_deref())
+    }
+
+    /// The session cookie, read from `session_file` if it is not set directly.
+    pub fn session(&self) -> Result<Option<String>, Error> {
+        match (&self.session, &self.session_file) {
+            (Some(session), _) => Ok(Some(session.clone())),
+            (None, Some(path)) => std::fs::read_to_string(path)
+                .map(|s| Some(s.trim().to_string()))
+                .map_err(|e| Error::Io(path.clone(), e)),
+            (None, None) => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn loading() {
+        let dir = std::env::temp_dir().join(format!("aoc-config-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(CONFIG_FILE);
+
+        assert_eq!(Config::default(), Config::load_from(&path).unwrap());
+
+        std::fs::write(&path, "mirror = \"mirror\"\nsession_file = \".session\"\n").unwrap();
+        std::fs::write(dir.join(".session"), "abc123\n").unwrap();
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(dir.join("input"), config.cache_dir);
+        assert_eq!(Some(dir.join("mirror")), config.mirror);
+        assert_eq!(Some("abc123".to_string()), config.session().unwrap());
+        assert_eq!(None, config.submit_url());
+
+        std::fs::write(&path, "submit_url = \"http://localhost:1\"\n").unwrap();
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(Some("http://localhost:1"), config.submit_url());
+
+        std::fs::write(&path, "mirror = \"m\"\nurl = \"http://localhost\"\n").unwrap();
+        assert!(matches!(
+            Config::load_from(&path),
+            Err(Error::TwoSources(_))
+        ));
+        std::fs::write(&path, "mirorr = \"m\"\n").unwrap();
+        assert!(matches!(Config::load_from(&path), Err(Error::Parse(..))));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,125 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use crate::{answers::DayAnswers, util::input};
+
+/// A puzzle example stored as `input/<year>/day<N>_example<K>.txt`, with the answers given in
+/// the puzzle text in `input/<year>/day<N>_example<K>.toml` (`part1 = ...`, `part2 = ...`).
+/// Either part may be left out when the puzzle only gives an answer for the other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub year: u16,
+    pub day: u8,
+    pub number: u8,
+    pub input: String,
+    pub expected: DayAnswers,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Io(PathBuf, std::io::Error),
+    Parse(PathBuf, toml::de::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io(path, e) => write!(f, "{}: {e}", path.display()),
+            Error::Parse(path, e) => write!(f, "{}: {e}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+pub fn input_path(year: u16, day: u8, number: u8) -> PathBuf {
+    PathBuf::from(format!("input/{year}/day{day}_example{number}.txt"))
+}
+
+pub fn answers_path(year: u16, day: u8, number: u8) -> PathBuf {
+    input_path(year, day, number).with_extension("toml")
+}
+
+fn read(path: &Path) -> Result<String, Error> {
+    std::fs::read_to_string(path).map_err(|e| Error::Io(path.to_path_buf(), e))
+}
+
+impl Example {
+    pub fn load(year: u16, day: u8, number: u8) -> Result<Self, Error> {
+        let input = input::normalize(&read(&input_path(year, day, number))?);
+        let answers_path = answers_path(year, day, number);
+        let expected = if answers_path.exists() {
+            let s = read(&answers_path)?;
+            toml::from_str(&s).map_err(|e| Error::Parse(answers_path, e))?
+        } else {
+            DayAnswers::default()
+        };
+        Ok(Self {
+            year,
+            day,
+            number,
+            input,
+            expected,
+        })
+    }
+}
+
+/// Every example for a day, in order, stopping at the first missing number.
+pub fn for_day(year: u16, day: u8) -> Result<Vec<Example>, Error> {
+    (1..=u8::MAX)
+        .take_while(|&n| input_path(year, day, n).exists())
+        .map(|n| Example::load(year, day, n))
+        .collect()
+}
+
+/// Reads and normalizes an example's input, for use in tests.
+pub fn input(year: u16, day: u8, number: u8) -> String {
+    let path = input_path(year, day, number);
+    input::read(&path).unwrap_or_else(|e| panic!("{}: {e}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        answers::{RecordedAnswer, Status},
+        registry::SOLUTIONS,
+        solution::Part,
+    };
+
+    #[test]
+    pub fn load_example() {
+        let examples = for_day(2022, 9).unwrap();
+        assert_eq!(2, examples.len());
+        assert_eq!(None, examples[1].expected.part1);
+        assert_eq!(
+            Some(RecordedAnswer::Integer(36)),
+            examples[1].expected.part2
+        );
+        assert!(examples[0].input.starts_with("R 4"));
+    }
+
+    #[test]
+    pub fn all_examples() {
+        for entry in SOLUTIONS {
+            for example in for_day(entry.year, entry.day).unwrap() {
+                for part in Part::ALL {
+                    let Some(expected) = example.expected.get(part) else {
+                        continue;
+                    };
+                    let actual = entry.run(part, &example.input).unwrap();
+                    assert_eq!(
+                        Status::Pass,
+                        Status::check(Some(expected), &actual),
+                        "{} day {} example {} part {part}",
+                        entry.year,
+                        entry.day,
+                        example.number
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use std::{
+    fmt::Display,
+    io::{Read, Write},
+    net::TcpStream,
+    time::Duration,
+};
+
+/// How long to wait for the server to accept, send or receive anything.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug)]
+pub enum Error {
+    /// Only plain `http://host[:port]/path` URLs are supported
+    Url(String),
+    Io(std::io::Error),
+    /// The server's reply was not an HTTP response
+    Malformed,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Url(url) => write!(f, "{url:?} is not an http://host[:port]/path URL"),
+            Error::Io(e) => write!(f, "{e}"),
+            Error::Malformed => write!(f, "the server did not send an HTTP response"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+struct Url<'a> {
+    host: &'a str,
+    port: u16,
+    path: &'a str,
+}
+
+fn parse_url(url: &str) -> Result<Url<'_>, Error> {
+    let invalid = || Error::Url(url.to_string());
+    let rest = url.strip_prefix("http://").ok_or_else(invalid)?;
+    let (authority, path) = rest.find('/').map_or((rest, "/"), |i| rest.split_at(i));
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => (host, port.parse().map_err(|_| invalid())?),
+        None => (authority, 80),
+    };
+    if host.is_empty() {
+        return Err(invalid());
+    }
+    Ok(Url { host, port, path })
+}
+
+fn parse_response(raw: &[u8]) -> Result<Response, Error> {
+    let text = String::from_utf8_lossy(raw);
+    let (head, body) = text.split_once("\r\n\r\n").ok_or(Error::Malformed)?;
+    let status = head
+        .lines()
+        .next()
+        .filter(|l| l.starts_with("HTTP/"))
+        .and_then(|l| l.split_whitespace().nth(1))
+        .and_then(|s| s.parse().ok())
+        .ok_or(Error::Malformed)?;
+    Ok(Response {
+        status,
+        body: body.to_string(),
+    })
+}
+
+/// Sends one HTTP/1.0 request, so the server closes the connection after a plain, unchunked
+/// response.
+pub fn request(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+) -> Result<Response, Error> {
+    let url = parse_url(url)?;
+    let mut stream = TcpStream::connect((url.host, url.port))?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+
+    let mut request = format!(
+        "{method} {} HTTP/1.0\r\nHost: {}\r\nUser-Agent: advent-of-code runner\r\n",
+        url.path, url.host
+    );
+    for (name, value) in headers {
+        request.push_str(&format!("{name}: {value}\r\n"));
+    }
+    if let Some(body) = body {
+        request.push_str(&format!("Content-Length: {}\r\n", body.len()));
+    }
+    request.push_str("\r\n");
+    request.push_str(body.unwrap_or_default());
+    stream.write_all(request.as_bytes())?;
+
+    let mut raw = Vec::new();
+    stream.read_to_end(&mut raw)?;
+    parse_response(&raw)
+}
+
+pub fn get(url: &str, headers: &[(&str, &str)]) -> Result<Response, Error> {
+    request("GET", url, headers, None)
+}
+
+pub fn post(url: &str, headers: &[(&str, &str)], body: &str) -> Result<Response, Error> {
+    request("POST", url, headers, Some(body))
+}
+
+/// A server on a free local port that answers one request with `response` and returns the
+/// request it received.
+#[cfg(test)]
+pub(crate) fn serve_once(response: &str) -> (String, std::thread::JoinHandle<String>) {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let response = response.to_string();
+    let handle = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut received = Vec::new();
+        let mut buf = [0; 1024];
+        while let Ok(n @ 1..) = stream.read(&mut buf) {
+            received.extend_from_slice(&buf[..n]);
+            let text = String::from_utf8_lossy(&received);
+            if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                let length = head
+                    .lines()
+                    .find_map(|l| l.strip_prefix("Content-Length: "))
+                    .map_or(0, |n| n.parse().unwrap());
+                if body.len() >= length {
+                    break;
+                }
+            }
+        }
+        stream.write_all(response.as_bytes()).unwrap();
+        String::from_utf8(received).unwrap()
+    });
+    (url, handle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn urls() {
+        let url = parse_url("http://localhost:8080/2022/day/1/input").unwrap();
+        assert_eq!(
+            ("localhost", 8080, "/2022/day/1/input"),
+            (url.host, url.port, url.path)
+        );
+        let url = parse_url("http://example.com").unwrap();
+        assert_eq!(("example.com", 80, "/"), (url.host, url.port, url.path));
+        assert!(parse_url("https://example.com").is_err());
+        assert!(parse_url("http://:80/").is_err());
+    }
+
+    #[test]
+    pub fn round_trip() {
+        let (url, server) =
+            serve_once("HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\n\r\nhello\n");
+        let response = post(
+            &format!("{url}/submit"),
+            &[("Cookie", "session=abc")],
+            "a=1",
+        )
+        .unwrap();
+        assert_eq!(
+            Response {
+                status: 200,
+                body: "hello\n".to_string()
+            },
+            response
+        );
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /submit HTTP/1.0\r\n"));
+        assert!(request.contains("\r\nCookie: session=abc\r\n"));
+        assert!(request.ends_with("\r\n\r\na=1"));
+
+        assert!(matches!(parse_response(b"garbage"), Err(Error::Malformed)));
+    }
+}
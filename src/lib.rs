@@ -1,3 +1,15 @@
+pub mod answers;
+pub mod bench;
+pub mod config;
+pub mod examples;
+pub mod http;
+pub mod parallel;
+pub mod provider;
+pub mod registry;
+pub mod scaffold;
+pub mod solution;
+pub mod submit;
+pub mod util;
 pub mod year2022;
 pub mod year2023;
 pub mod year2025;
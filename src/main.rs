@@ -1,7 +1,694 @@
-pub mod year2022;
-pub mod year2023;
-pub mod year2025;
+use std::{
+    collections::BTreeMap,
+    io::Read,
+    panic::AssertUnwindSafe,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::Arc,
+    time::Duration,
+};
 
-pub fn main() {
-    dbg!(year2025::day5::main());
+use advent_of_code::{
+    answers::{Answers, Status},
+    bench,
+    config::Config,
+    examples, parallel,
+    provider::Provider,
+    registry::{self, Entry},
+    scaffold,
+    solution::{Answer, Part},
+    submit::{self, Outcome},
+    util,
+};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+#[derive(Parser)]
+#[command(about = "Run Advent of Code solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run one day, every day of a year, or every day with --all
+    Run {
+        #[arg(required_unless_present = "all")]
+        year: Option<u16>,
+        day: Option<u8>,
+        /// Only run this part (1 or 2)
+        #[arg(short, long, value_parser = parse_part)]
+        part: Option<Part>,
+        /// Input file, or `-` for stdin. Defaults to the cached input, fetched if missing
+        #[arg(short, long, requires = "day", conflicts_with = "all")]
+        input: Option<PathBuf>,
+        /// Use input/<year>/day<N>_example<K>.txt as the input
+        #[arg(short, long, value_name = "K", requires = "day", conflicts_with_all = ["all", "input"])]
+        example: Option<u8>,
+        /// Run every registered day
+        #[arg(long, conflicts_with = "year")]
+        all: bool,
+        #[command(flatten)]
+        pool: Pool,
+    },
+    /// Draw how a day solved each part, for days that support it
+    Render {
+        year: u16,
+        day: u8,
+        /// Only draw this part (1 or 2)
+        #[arg(short, long, value_parser = parse_part)]
+        part: Option<Part>,
+        /// Input file, or `-` for stdin. Defaults to the cached input, fetched if missing
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+        /// Use input/<year>/day<N>_example<K>.txt as the input
+        #[arg(short, long, value_name = "K", conflicts_with = "input")]
+        example: Option<u8>,
+    },
+    /// List the registered days
+    List,
+    /// Check answers on the real inputs against input/<year>/answers.toml
+    Verify {
+        year: Option<u16>,
+        #[arg(requires = "year")]
+        day: Option<u8>,
+        /// Check the example fixtures against their expected answers instead
+        #[arg(long, conflicts_with = "record")]
+        examples: bool,
+        /// Save the computed answers for days that have none recorded
+        #[arg(long)]
+        record: bool,
+        #[command(flatten)]
+        pool: Pool,
+    },
+    /// Post the answer to one part on the real input and record whether it was right
+    Submit {
+        year: u16,
+        day: u8,
+        #[arg(value_parser = parse_part)]
+        part: Part,
+    },
+    /// Time parsing and each part on the real inputs
+    Bench {
+        year: Option<u16>,
+        #[arg(requires = "year")]
+        day: Option<u8>,
+        /// Number of times to run each day
+        #[arg(short = 'n', long, default_value_t = 10)]
+        iterations: usize,
+        #[arg(short, long, value_enum, default_value_t = BenchFormat::Markdown)]
+        format: BenchFormat,
+        /// Time on input/<year>/day<N>_example<K>.txt instead of the real input
+        #[arg(short, long, value_name = "K")]
+        example: Option<u8>,
+    },
+
+    /// Create a new day from the year's template and register it
+    New { year: u16, day: u8 },
+}
+
+/// How days are spread over threads. Results are still printed in day order.
+#[derive(Args)]
+struct Pool {
+    /// Number of days to run at once
+    #[arg(short, long, default_value_t = parallel::default_jobs())]
+    jobs: usize,
+    /// Give up on a day that takes longer than this many seconds
+    #[arg(long, value_name = "SECONDS", value_parser = parse_seconds)]
+    timeout: Option<Duration>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum BenchFormat {
+    Markdown,
+    Json,
+}
+
+fn parse_part(s: &str) -> Result<Part, String> {
+    s.parse()
+        .map_err(|_| format!("invalid part {s:?}, expected 1 or 2"))
+}
+
+fn parse_seconds(s: &str) -> Result<Duration, String> {
+    s.parse()
+        .ok()
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+        .ok_or_else(|| format!("invalid number of seconds {s:?}"))
+}
+
+fn read_input(path: &Path) -> std::io::Result<String> {
+    if path == Path::new("-") {
+        let mut input = String::new();
+        std::io::stdin().read_to_string(&mut input)?;
+        Ok(util::input::normalize(&input))
+    } else {
+        util::input::read(path)
+    }
+}
+
+/// The provider configured by `aoc.toml`, reporting a broken configuration.
+fn load_provider() -> Option<Provider> {
+    Provider::from_env().map_err(|e| eprintln!("{e}")).ok()
+}
+
+/// Reads `input`, or the entry's real input if there is none.
+fn load_input(provider: &Provider, entry: &Entry, input: Option<&Path>) -> Result<String, String> {
+    let Some(path) = input else {
+        return provider
+            .input(entry.year, entry.day)
+            .map_err(|e| e.to_string());
+    };
+    read_input(path).map_err(|e| {
+        format!(
+            "{} day {}: could not read {}: {e}",
+            entry.year,
+            entry.day,
+            path.display()
+        )
+    })
+}
+
+fn solve_entry(
+    provider: &Provider,
+    entry: &Entry,
+    part: Option<Part>,
+    input: Option<&Path>,
+) -> Result<Vec<(Part, Answer)>, String> {
+    let input = load_input(provider, entry, input)?;
+    let answers = match part {
+        Some(part) => entry.run(part, &input).map(|a| vec![(part, a)]),
+        None => entry
+            .run_both(&input)
+            .map(|answers| Part::ALL.into_iter().zip(answers).collect()),
+    };
+    answers.map_err(|e| format!("{} day {}: {e}", entry.year, entry.day))
+}
+
+fn select_entries(year: Option<u16>, day: Option<u8>) -> Result<Vec<&'static Entry>, String> {
+    let entries: Vec<_> = match (year, day) {
+        (Some(year), Some(day)) => match registry::find(year, day) {
+            Some(e) => vec![e],
+            None => return Err(format!("{year} day {day} is not registered")),
+        },
+        (Some(year), None) => registry::for_year(year).collect(),
+        (None, _) => registry::SOLUTIONS.iter().collect(),
+    };
+    if entries.is_empty() {
+        Err(format!(
+            "no days registered for {}",
+            year.unwrap_or_default()
+        ))
+    } else {
+        Ok(entries)
+    }
+}
+
+fn run(
+    year: Option<u16>,
+    day: Option<u8>,
+    part: Option<Part>,
+    input: Option<PathBuf>,
+    pool: Pool,
+) -> ExitCode {
+    let entries = match select_entries(year, day) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let Some(provider) = load_provider().map(Arc::new) else {
+        return ExitCode::FAILURE;
+    };
+    let mut ok = true;
+    parallel::run_ordered(
+        entries.clone(),
+        pool.jobs,
+        pool.timeout,
+        move |entry| solve_entry(&provider, entry, part, input.as_deref()),
+        |index, result| {
+            let entry = entries[index];
+            match result {
+                Ok(Ok(answers)) => {
+                    for (part, answer) in answers {
+                        if answer.is_multiline() {
+                            println!("{} day {} part {part}:\n{answer}", entry.year, entry.day);
+                        } else {
+                            println!("{} day {} part {part}: {answer}", entry.year, entry.day);
+                        }
+                    }
+                }
+                Ok(Err(e)) => {
+                    eprintln!("{e}");
+                    ok = false;
+                }
+                Err(failure) => {
+                    eprintln!("{} day {}: {failure}", entry.year, entry.day);
+                    ok = false;
+                }
+            }
+        },
+    );
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn render(year: u16, day: u8, part: Option<Part>, input: Option<&Path>) -> ExitCode {
+    let Some(entry) = registry::find(year, day) else {
+        eprintln!("{year} day {day} is not registered");
+        return ExitCode::FAILURE;
+    };
+    let Some(provider) = load_provider() else {
+        return ExitCode::FAILURE;
+    };
+    let input = match load_input(&provider, entry, input) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let parts = part.map_or(Part::ALL.to_vec(), |p| vec![p]);
+    for part in parts {
+        match entry.solution.render(&input, part) {
+            Ok(Some(picture)) => println!("{year} day {day} part {part}:\n{picture}"),
+            Err(e) => {
+                eprintln!("{year} day {day}: {e}");
+                return ExitCode::FAILURE;
+            }
+            Ok(None) => {
+                eprintln!("{year} day {day} part {part} has no rendering");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn list() -> ExitCode {
+    for entry in registry::SOLUTIONS {
+        println!("{} day {}", entry.year, entry.day);
+    }
+    ExitCode::SUCCESS
+}
+
+/// Runs the given parts on `input`, turning a missing or malformed input or a panic into an
+/// error for each part.
+fn solve_catching(
+    entry: &Entry,
+    parts: &[Part],
+    input: Result<String, String>,
+) -> Vec<(Part, Result<Answer, String>)> {
+    let result = input.and_then(|input| {
+        std::panic::catch_unwind(AssertUnwindSafe(|| match parts {
+            [part] => entry.run(*part, &input).map(|a| vec![a]),
+            _ => entry.run_both(&input).map(|answers| answers.to_vec()),
+        }))
+        .map_err(|panic| parallel::panic_message(panic.as_ref()))
+        .and_then(|answers| answers.map_err(|e| e.to_string()))
+    });
+    match result {
+        Ok(answers) => parts
+            .iter()
+            .copied()
+            .zip(answers.into_iter().map(Ok))
+            .collect(),
+        Err(e) => failed_parts(parts, e),
+    }
+}
+
+fn print_status(label: &str, status: &Status, actual: Option<&Answer>) {
+    match (status, actual) {
+        (Status::Pass, _) => println!("{label}: pass"),
+        (Status::Missing, Some(actual)) if actual.is_multiline() => {
+            println!("{label}: missing, got\n{actual}")
+        }
+        (Status::Missing, Some(actual)) => println!("{label}: missing, got {actual}"),
+        (Status::Changed { expected }, Some(actual)) if actual.is_multiline() => {
+            println!("{label}: CHANGED, expected\n{expected}\ngot\n{actual}")
+        }
+        (Status::Changed { expected }, Some(actual)) => {
+            println!("{label}: CHANGED, expected {expected}, got {actual}")
+        }
+        (Status::Failed(e), _) => println!("{label}: FAILED, {e}"),
+        (_, None) => unreachable!("only failures have no answer"),
+    }
+}
+
+#[derive(Default)]
+struct Tally {
+    passed: usize,
+    missing: usize,
+    changed: usize,
+    failed: usize,
+}
+
+impl Tally {
+    fn add(&mut self, status: &Status) {
+        match status {
+            Status::Pass => self.passed += 1,
+            Status::Missing => self.missing += 1,
+            Status::Changed { .. } => self.changed += 1,
+            Status::Failed(_) => self.failed += 1,
+        }
+    }
+
+    fn report(&self) -> ExitCode {
+        println!(
+            "{} passed, {} missing, {} changed, {} failed",
+            self.passed, self.missing, self.changed, self.failed
+        );
+        if self.changed == 0 && self.failed == 0 {
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// The result of each part of a day, for days that failed as a whole.
+fn failed_parts(parts: &[Part], e: String) -> Vec<(Part, Result<Answer, String>)> {
+    parts.iter().map(|&p| (p, Err(e.clone()))).collect()
+}
+
+fn verify_inputs(
+    provider: Provider,
+    entries: Vec<&'static Entry>,
+    record: bool,
+    pool: Pool,
+) -> ExitCode {
+    let mut stores: BTreeMap<u16, (Answers, bool)> = BTreeMap::new();
+    for entry in &entries {
+        if stores.contains_key(&entry.year) {
+            continue;
+        }
+        match Answers::load(entry.year) {
+            Ok(answers) => stores.insert(entry.year, (answers, false)),
+            Err(e) => {
+                eprintln!("{e}");
+                return ExitCode::FAILURE;
+            }
+        };
+    }
+
+    let mut tally = Tally::default();
+    parallel::run_ordered(
+        entries.clone(),
+        pool.jobs,
+        pool.timeout,
+        move |entry| {
+            let input = provider
+                .input(entry.year, entry.day)
+                .map_err(|e| e.to_string());
+            solve_catching(entry, &Part::ALL, input)
+        },
+        |index, results| {
+            let entry = entries[index];
+            let (answers, dirty) = stores.get_mut(&entry.year).unwrap();
+            let results =
+                results.unwrap_or_else(|failure| failed_parts(&Part::ALL, failure.to_string()));
+            for (part, result) in results {
+                let status = match &result {
+                    Ok(actual) => Status::check(answers.get(entry.day, part), actual),
+                    Err(e) => Status::Failed(e.clone()),
+                };
+                let label = format!("{} day {} part {part}", entry.year, entry.day);
+                print_status(&label, &status, result.as_ref().ok());
+                tally.add(&status);
+
+                if let (true, Status::Missing, Ok(actual)) = (record, &status, &result) {
+                    answers.record(entry.day, part, actual);
+                    *dirty = true;
+                }
+            }
+        },
+    );
+
+    for (year, (answers, dirty)) in stores {
+        if dirty && let Err(e) = answers.save(year) {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    }
+
+    tally.report()
+}
+
+/// Checks each day against its example fixtures. Parts without an expected answer are skipped.
+fn verify_examples(entries: Vec<&'static Entry>, pool: Pool) -> ExitCode {
+    let mut tally = Tally::default();
+    let mut ok = true;
+    parallel::run_ordered(
+        entries.clone(),
+        pool.jobs,
+        pool.timeout,
+        |entry| {
+            let examples = examples::for_day(entry.year, entry.day).map_err(|e| e.to_string())?;
+            Ok::<_, String>(
+                examples
+                    .into_iter()
+                    .map(|example| {
+                        let parts: Vec<Part> = Part::ALL
+                            .into_iter()
+                            .filter(|&p| example.expected.get(p).is_some())
+                            .collect();
+                        let results = solve_catching(entry, &parts, Ok(example.input));
+                        (example.number, example.expected, results)
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        },
+        |index, examples| {
+            let entry = entries[index];
+            let examples = match examples {
+                Ok(Ok(examples)) => examples,
+                Ok(Err(e)) => {
+                    eprintln!("{e}");
+                    ok = false;
+                    return;
+                }
+                Err(failure) => {
+                    eprintln!("{} day {} examples: {failure}", entry.year, entry.day);
+                    tally.add(&Status::Failed(failure.to_string()));
+                    return;
+                }
+            };
+            for (number, expected, results) in examples {
+                for (part, result) in results {
+                    let status = match &result {
+                        Ok(actual) => Status::check(expected.get(part), actual),
+                        Err(e) => Status::Failed(e.clone()),
+                    };
+                    let label = format!(
+                        "{} day {} example {number} part {part}",
+                        entry.year, entry.day
+                    );
+                    print_status(&label, &status, result.as_ref().ok());
+                    tally.add(&status);
+                }
+            }
+        },
+    );
+    let report = tally.report();
+    if ok { report } else { ExitCode::FAILURE }
+}
+
+fn verify(
+    year: Option<u16>,
+    day: Option<u8>,
+    examples: bool,
+    record: bool,
+    pool: Pool,
+) -> ExitCode {
+    let entries = match select_entries(year, day) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if examples {
+        verify_examples(entries, pool)
+    } else if let Some(provider) = load_provider() {
+        verify_inputs(provider, entries, record, pool)
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn submit(year: u16, day: u8, part: Part) -> ExitCode {
+    let Some(entry) = registry::find(year, day) else {
+        eprintln!("{year} day {day} is not registered");
+        return ExitCode::FAILURE;
+    };
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let client = match submit::Client::from_config(&config) {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let input = Provider::from_config(&config).and_then(|p| p.input(year, day));
+    let answer = match input.map(|input| entry.run(part, &input)) {
+        Ok(Ok(answer)) => answer,
+        Ok(Err(e)) => {
+            eprintln!("{year} day {day}: {e}");
+            return ExitCode::FAILURE;
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut answers = match Answers::load(year) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let before = answers.clone();
+    let outcome = match client.submit(&mut answers, year, day, part, &answer) {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            eprintln!("{year} day {day} part {part}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    println!("{year} day {day} part {part}: {answer} is {outcome}");
+    if answers != before
+        && let Err(e) = answers.save(year)
+    {
+        eprintln!("{e}");
+        return ExitCode::FAILURE;
+    }
+    if outcome == Outcome::Correct {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn run_bench(
+    year: Option<u16>,
+    day: Option<u8>,
+    iterations: usize,
+    format: BenchFormat,
+    example: Option<u8>,
+) -> ExitCode {
+    let entries = match select_entries(year, day) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let Some(provider) = load_provider() else {
+        return ExitCode::FAILURE;
+    };
+    let mut results = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let input = match example {
+            Some(k) => {
+                let path = examples::input_path(entry.year, entry.day, k);
+                util::input::read(&path)
+                    .map_err(|e| format!("could not read {}: {e}", path.display()))
+            }
+            None => provider
+                .input(entry.year, entry.day)
+                .map_err(|e| e.to_string()),
+        };
+        match input {
+            Ok(input) => {
+                eprintln!("{} day {}...", entry.year, entry.day);
+                match bench::bench(entry, &input, iterations) {
+                    Ok(result) => results.push(result),
+                    Err(e) => eprintln!("skipping {} day {}: {e}", entry.year, entry.day),
+                }
+            }
+            Err(e) => eprintln!("skipping {} day {}: {e}", entry.year, entry.day),
+        }
+    }
+
+    let years = bench::by_year(results);
+    match format {
+        BenchFormat::Markdown => print!("{}", bench::to_markdown(&years)),
+        BenchFormat::Json => println!("{}", bench::to_json(&years)),
+    }
+    ExitCode::SUCCESS
+}
+
+fn new_day(year: u16, day: u8) -> ExitCode {
+    match scaffold::new_day(Path::new("."), year, day) {
+        Ok(touched) => {
+            for path in touched {
+                println!("{}", path.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+pub fn main() -> ExitCode {
+    match Cli::parse().command {
+        Command::Run {
+            year,
+            day,
+            part,
+            input,
+            example,
+            all: _,
+            pool,
+        } => {
+            let input = match (year, day, example) {
+                (Some(year), Some(day), Some(k)) => Some(examples::input_path(year, day, k)),
+                _ => input,
+            };
+            run(year, day, part, input, pool)
+        }
+        Command::Render {
+            year,
+            day,
+            part,
+            input,
+            example,
+        } => {
+            let input = example.map_or(input, |k| Some(examples::input_path(year, day, k)));
+            render(year, day, part, input.as_deref())
+        }
+        Command::List => list(),
+        Command::Verify {
+            year,
+            day,
+            examples,
+            record,
+            pool,
+        } => verify(year, day, examples, record, pool),
+        Command::Submit { year, day, part } => submit(year, day, part),
+        Command::Bench {
+            year,
+            day,
+            iterations,
+            format,
+            example,
+        } => run_bench(year, day, iterations, format, example),
+        Command::New { year, day } => new_day(year, day),
+    }
 }
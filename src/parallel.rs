@@ -0,0 +1,157 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    panic::AssertUnwindSafe,
+    sync::{Arc, Mutex, mpsc},
+    thread,
+    time::Duration,
+};
+
+/// Why a job gave no result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    Panicked(String),
+    /// Still running when the timeout ran out. Threads cannot be stopped, so it is left to finish
+    /// in the background and its result is thrown away.
+    TimedOut(Duration),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Panicked(message) => write!(f, "{message}"),
+            Failure::TimedOut(timeout) => write!(f, "timed out after {timeout:?}"),
+        }
+    }
+}
+
+impl std::error::Error for Failure {}
+
+pub fn panic_message(panic: &(dyn std::any::Any + Send)) -> String {
+    panic
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "panicked".to_string())
+}
+
+/// The number of jobs to run at once when none is asked for.
+pub fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Runs `job` on a fresh thread and waits for it for at most `timeout`.
+fn run_one<I, T, F>(job: &Arc<F>, item: I, timeout: Option<Duration>) -> Result<T, Failure>
+where
+    I: Send + 'static,
+    T: Send + 'static,
+    F: Fn(I) -> T + Send + Sync + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    let job = Arc::clone(job);
+    thread::spawn(move || {
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| job(item)))
+            .map_err(|panic| Failure::Panicked(panic_message(panic.as_ref())));
+        let _ = sender.send(result);
+    });
+    match timeout {
+        Some(timeout) => receiver
+            .recv_timeout(timeout)
+            .unwrap_or(Err(Failure::TimedOut(timeout))),
+        None => receiver
+            .recv()
+            .unwrap_or_else(|_| Err(Failure::Panicked("panicked".to_string()))),
+    }
+}
+
+/// Runs `job` on every item, up to `jobs` at a time, each for at most `timeout`. `emit` is called
+/// on the calling thread with the index and result of every item in the order of `items`, as soon
+/// as it and all those before it are done.
+pub fn run_ordered<I, T, F>(
+    items: Vec<I>,
+    jobs: usize,
+    timeout: Option<Duration>,
+    job: F,
+    mut emit: impl FnMut(usize, Result<T, Failure>),
+) where
+    I: Send + 'static,
+    T: Send + 'static,
+    F: Fn(I) -> T + Send + Sync + 'static,
+{
+    let workers = jobs.clamp(1, items.len().max(1));
+    let queue = Arc::new(Mutex::new(items.into_iter().enumerate()));
+    let job = Arc::new(job);
+    let (sender, receiver) = mpsc::channel();
+    for _ in 0..workers {
+        let queue = Arc::clone(&queue);
+        let job = Arc::clone(&job);
+        let sender = sender.clone();
+        thread::spawn(move || {
+            loop {
+                let Some((index, item)) = queue.lock().unwrap().next() else {
+                    break;
+                };
+                if sender.send((index, run_one(&job, item, timeout))).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    drop(sender);
+
+    let mut finished = BTreeMap::new();
+    let mut next = 0;
+    for (index, result) in receiver {
+        finished.insert(index, result);
+        while let Some(result) = finished.remove(&next) {
+            emit(next, result);
+            next += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn stable_order() {
+        let mut results = Vec::new();
+        run_ordered(
+            (0..8u64).collect(),
+            4,
+            None,
+            |i| {
+                thread::sleep(Duration::from_millis(40 - 5 * i));
+                i * i
+            },
+            |index, result| results.push((index, result.unwrap())),
+        );
+        let squares: Vec<_> = (0..8).map(|i| (i as usize, i * i)).collect();
+        assert_eq!(squares, results);
+    }
+
+    #[test]
+    pub fn failures() {
+        let mut results = Vec::new();
+        run_ordered(
+            vec![0, 1, 2],
+            1,
+            Some(Duration::from_millis(200)),
+            |i| match i {
+                0 => thread::sleep(Duration::from_secs(10)),
+                1 => panic!("bad input"),
+                _ => {}
+            },
+            |_, result| results.push(result),
+        );
+        assert_eq!(
+            vec![
+                Err(Failure::TimedOut(Duration::from_millis(200))),
+                Err(Failure::Panicked("bad input".to_string())),
+                Ok(())
+            ],
+            results
+        );
+    }
+}
@@ -0,0 +1,286 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    config::{self, Config},
+    http,
+    util::input,
+};
+
+/// Somewhere to get puzzle inputs that are not cached yet.
+pub trait Fetcher: Send + Sync {
+    /// The raw input for `year` `day`, or why it could not be had.
+    fn fetch(&self, year: u16, day: u8) -> Result<String, String>;
+
+    /// Where inputs come from, for error messages.
+    fn source(&self) -> String;
+}
+
+/// A directory laid out like the cache, such as a checkout of someone's inputs.
+#[derive(Debug, Clone)]
+pub struct Mirror {
+    pub root: PathBuf,
+}
+
+impl Fetcher for Mirror {
+    fn fetch(&self, year: u16, day: u8) -> Result<String, String> {
+        let path = cache_path(&self.root, year, day);
+        std::fs::read_to_string(&path).map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    fn source(&self) -> String {
+        self.root.display().to_string()
+    }
+}
+
+/// A server answering `GET <base_url>/<year>/day/<day>/input` like the puzzle site.
+#[derive(Debug, Clone)]
+pub struct Server {
+    pub base_url: String,
+    pub session: Option<String>,
+}
+
+impl Fetcher for Server {
+    fn fetch(&self, year: u16, day: u8) -> Result<String, String> {
+        let url = format!(
+            "{}/{year}/day/{day}/input",
+            self.base_url.trim_end_matches('/')
+        );
+        let cookie = self.session.as_ref().map(|s| format!("session={s}"));
+        let headers: Vec<(&str, &str)> = cookie.iter().map(|c| ("Cookie", c.as_str())).collect();
+        let response = http::get(&url, &headers).map_err(|e| e.to_string())?;
+        if response.is_success() {
+            Ok(response.body)
+        } else {
+            Err(format!("{url} answered HTTP {}", response.status))
+        }
+    }
+
+    fn source(&self) -> String {
+        self.base_url.clone()
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Config(config::Error),
+    Io(PathBuf, std::io::Error),
+    /// Not cached and there is nowhere to fetch it from
+    Missing {
+        year: u16,
+        day: u8,
+        path: PathBuf,
+    },
+    Fetch {
+        year: u16,
+        day: u8,
+        source: String,
+        reason: String,
+    },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Config(e) => write!(f, "{e}"),
+            Error::Io(path, e) => write!(f, "{}: {e}", path.display()),
+            Error::Missing { year, day, path } => write!(
+                f,
+                "no input for {year} day {day}: {} is missing or empty, and no `mirror` or `url` \
+                 is set in {} to fetch it from",
+                path.display(),
+                config::CONFIG_FILE
+            ),
+            Error::Fetch {
+                year,
+                day,
+                source,
+                reason,
+            } => write!(
+                f,
+                "could not fetch {year} day {day} from {source}: {reason}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<config::Error> for Error {
+    fn from(e: config::Error) -> Self {
+        Error::Config(e)
+    }
+}
+
+fn cache_path(root: &Path, year: u16, day: u8) -> PathBuf {
+    root.join(year.to_string()).join(format!("day{day}.txt"))
+}
+
+/// Real puzzle inputs, read from the cache directory and fetched into it when missing.
+pub struct Provider {
+    cache_dir: PathBuf,
+    fetcher: Option<Box<dyn Fetcher>>,
+}
+
+impl Provider {
+    /// A provider that only reads what is already in `cache_dir`.
+    pub fn new(cache_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            cache_dir: cache_dir.into(),
+            fetcher: None,
+        }
+    }
+
+    pub fn with_fetcher(mut self, fetcher: impl Fetcher + 'static) -> Self {
+        self.fetcher = Some(Box::new(fetcher));
+        self
+    }
+
+    pub fn from_config(config: &Config) -> Result<Self, Error> {
+        let provider = Self::new(&config.cache_dir);
+        Ok(match (&config.mirror, &config.url) {
+            (Some(root), _) => provider.with_fetcher(Mirror { root: root.clone() }),
+            (None, Some(url)) => provider.with_fetcher(Server {
+                base_url: url.clone(),
+                session: config.session()?,
+            }),
+            (None, None) => provider,
+        })
+    }
+
+    /// The provider set up by `aoc.toml`.
+    pub fn from_env() -> Result<Self, Error> {
+        Self::from_config(&Config::load()?)
+    }
+
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        cache_path(&self.cache_dir, year, day)
+    }
+
+    /// The normalized input for `year` `day`. An input that is not cached, or is an empty file as
+    /// left by the `new` command, is fetched and saved to the cache first.
+    pub fn input(&self, year: u16, day: u8) -> Result<String, Error> {
+        let path = self.path(year, day);
+        match std::fs::read_to_string(&path) {
+            Ok(raw) if !raw.trim().is_empty() => return Ok(input::normalize(&raw)),
+            Ok(_) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(Error::Io(path, e)),
+        }
+
+        let Some(fetcher) = &self.fetcher else {
+            return Err(Error::Missing { year, day, path });
+        };
+        let fetch_error = |reason| Error::Fetch {
+            year,
+            day,
+            source: fetcher.source(),
+            reason,
+        };
+        let raw = fetcher.fetch(year, day).map_err(fetch_error)?;
+        if raw.trim().is_empty() {
+            return Err(fetch_error("the input is empty".to_string()));
+        }
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| Error::Io(parent.to_path_buf(), e))?;
+        }
+        std::fs::write(&path, &raw).map_err(|e| Error::Io(path.clone(), e))?;
+        Ok(input::normalize(&raw))
+    }
+}
+
+/// The input for `year` `day` from the provider set up by `aoc.toml`.
+pub fn input(year: u16, day: u8) -> Result<String, Error> {
+    Provider::from_env()?.input(year, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-provider-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    pub fn cache_only() {
+        let cache = temp_dir("cache");
+        let provider = Provider::new(&cache);
+        let error = provider.input(2022, 1).unwrap_err();
+        assert!(matches!(
+            error,
+            Error::Missing {
+                year: 2022,
+                day: 1,
+                ..
+            }
+        ));
+        assert!(error.to_string().contains("day1.txt is missing or empty"));
+
+        std::fs::create_dir_all(cache.join("2022")).unwrap();
+        std::fs::write(provider.path(2022, 1), "").unwrap();
+        assert!(matches!(
+            provider.input(2022, 1),
+            Err(Error::Missing { .. })
+        ));
+        std::fs::write(provider.path(2022, 1), "1\r\n2\r\n").unwrap();
+        assert_eq!("1\n2\n", provider.input(2022, 1).unwrap());
+
+        std::fs::remove_dir_all(&cache).unwrap();
+    }
+
+    #[test]
+    pub fn from_mirror() {
+        let cache = temp_dir("mirrored");
+        let mirror = temp_dir("mirror");
+        std::fs::create_dir_all(mirror.join("2025")).unwrap();
+        std::fs::write(mirror.join("2025").join("day3.txt"), "987\n").unwrap();
+        let provider = Provider::new(&cache).with_fetcher(Mirror {
+            root: mirror.clone(),
+        });
+
+        assert_eq!("987\n", provider.input(2025, 3).unwrap());
+        assert_eq!(
+            "987\n",
+            std::fs::read_to_string(provider.path(2025, 3)).unwrap()
+        );
+        let error = provider.input(2025, 4).unwrap_err();
+        assert!(matches!(error, Error::Fetch { day: 4, .. }));
+
+        std::fs::remove_dir_all(&cache).unwrap();
+        std::fs::remove_dir_all(&mirror).unwrap();
+    }
+
+    #[test]
+    pub fn from_server() {
+        let cache = temp_dir("downloaded");
+        let (url, server) = http::serve_once("HTTP/1.1 200 OK\r\n\r\n1000\n2000\n");
+        let provider = Provider::new(&cache).with_fetcher(Server {
+            base_url: url,
+            session: Some("abc".to_string()),
+        });
+
+        assert_eq!("1000\n2000\n", provider.input(2022, 1).unwrap());
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2022/day/1/input HTTP/1.0\r\n"));
+        assert!(request.contains("\r\nCookie: session=abc\r\n"));
+        // cached now, so the server is not asked again
+        assert_eq!("1000\n2000\n", provider.input(2022, 1).unwrap());
+
+        let (url, server) = http::serve_once("HTTP/1.1 404 Not Found\r\n\r\n");
+        let provider = Provider::new(&cache).with_fetcher(Server {
+            base_url: url,
+            session: None,
+        });
+        let error = provider.input(2022, 2).unwrap_err().to_string();
+        assert!(error.contains("answered HTTP 404"), "{error}");
+        server.join().unwrap();
+
+        std::fs::remove_dir_all(&cache).unwrap();
+    }
+}
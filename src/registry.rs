@@ -0,0 +1,98 @@
+pub use crate::solution::Part;
+use crate::{
+    solution::{Answer, DynSolution},
+    util::ParseError,
+};
+
+pub struct Entry {
+    pub year: u16,
+    pub day: u8,
+    pub solution: &'static dyn DynSolution,
+}
+
+impl Entry {
+    pub fn run(&self, part: Part, input: &str) -> Result<Answer, ParseError> {
+        self.solution.solve(input, part)
+    }
+
+    pub fn run_both(&self, input: &str) -> Result<[Answer; 2], ParseError> {
+        self.solution.solve_both(input)
+    }
+}
+
+macro_rules! entry {
+    ($year:literal, $day:literal, $year_mod:ident::$day_mod:ident) => {
+        Entry {
+            year: $year,
+            day: $day,
+            solution: &crate::$year_mod::$day_mod::Day,
+        }
+    };
+}
+
+/// Every solved day, ordered by year then day.
+pub static SOLUTIONS: &[Entry] = &[
+    entry!(2022, 1, year2022::day1),
+    entry!(2022, 2, year2022::day2),
+    entry!(2022, 3, year2022::day3),
+    entry!(2022, 4, year2022::day4),
+    entry!(2022, 5, year2022::day5),
+    entry!(2022, 6, year2022::day6),
+    entry!(2022, 7, year2022::day7),
+    entry!(2022, 8, year2022::day8),
+    entry!(2022, 9, year2022::day9),
+    entry!(2022, 10, year2022::day10),
+    entry!(2022, 11, year2022::day11),
+    entry!(2022, 12, year2022::day12),
+    entry!(2022, 13, year2022::day13),
+    entry!(2022, 14, year2022::day14),
+    entry!(2023, 16, year2023::day16),
+    entry!(2025, 1, year2025::day1),
+    entry!(2025, 2, year2025::day2),
+    entry!(2025, 3, year2025::day3),
+    entry!(2025, 4, year2025::day4),
+    entry!(2025, 5, year2025::day5),
+];
+
+pub fn find(year: u16, day: u8) -> Option<&'static Entry> {
+    SOLUTIONS.iter().find(|e| e.year == year && e.day == day)
+}
+
+pub fn for_year(year: u16) -> impl Iterator<Item = &'static Entry> {
+    SOLUTIONS.iter().filter(move |e| e.year == year)
+}
+
+pub fn run(year: u16, day: u8, part: Part, input: &str) -> Option<Result<Answer, ParseError>> {
+    find(year, day).map(|e| e.run(part, input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn sorted_and_unique() {
+        assert!(
+            SOLUTIONS
+                .windows(2)
+                .all(|w| (w[0].year, w[0].day) < (w[1].year, w[1].day))
+        );
+    }
+
+    #[test]
+    pub fn lookup() {
+        assert!(find(2022, 12).is_some());
+        assert!(find(2023, 1).is_none());
+        assert_eq!(14, for_year(2022).count());
+        assert_eq!(
+            Some(Ok(Answer::Integer(15))),
+            run(2022, 2, Part::One, "A Y\nB X\nC Z")
+        );
+        assert_eq!(
+            Some(Ok(Answer::Integer(12))),
+            run(2022, 2, Part::Two, "A Y\nB X\nC Z")
+        );
+        let error = run(2022, 2, Part::One, "A Y\nB Q").unwrap().unwrap_err();
+        assert_eq!((2, 3), (error.line, error.column));
+    }
+}
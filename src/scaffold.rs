@@ -0,0 +1,321 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(PathBuf, std::io::Error),
+    AlreadyExists(PathBuf),
+    NoTemplate,
+    /// The file has no existing line to place the new one next to
+    NoInsertionPoint(PathBuf),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io(path, e) => write!(f, "{}: {e}", path.display()),
+            Error::AlreadyExists(path) => write!(f, "{} already exists", path.display()),
+            Error::NoTemplate => write!(f, "no src/yearYYYY/template.rs to copy from"),
+            Error::NoInsertionPoint(path) => {
+                write!(f, "could not find where to register in {}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+fn read(path: &Path) -> Result<String, Error> {
+    std::fs::read_to_string(path).map_err(|e| Error::Io(path.to_path_buf(), e))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), Error> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| Error::Io(parent.to_path_buf(), e))?;
+    }
+    std::fs::write(path, contents).map_err(|e| Error::Io(path.to_path_buf(), e))
+}
+
+static INPUT_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?-u)provider::input\(\d+, n\)").unwrap());
+static EXAMPLE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?-u)examples::input\(\d+, n, ").unwrap());
+
+/// Points the template's `provider::input(YYYY, n)` and `examples::input(YYYY, n, ..)`
+/// placeholders at `year`, and at the given day if there is one.
+pub fn render_template(template: &str, year: u16, day: Option<u8>) -> String {
+    let day = day.map_or_else(|| "n".to_string(), |d| d.to_string());
+    let rendered = INPUT_REGEX.replace_all(template, format!("provider::input({year}, {day})"));
+    EXAMPLE_REGEX
+        .replace_all(&rendered, format!("examples::input({year}, {day}, "))
+        .into_owned()
+}
+
+/// Inserts `line` among the lines that `key` gives a number for, keeping them in order of that
+/// number. Attribute lines directly above an existing line stay attached to it.
+/// Returns `None` if no line has a key.
+pub fn insert_sorted(
+    source: &str,
+    key: impl Fn(&str) -> Option<u32>,
+    line: &str,
+) -> Option<String> {
+    let number = key(line)?;
+    let lines: Vec<&str> = source.lines().collect();
+    let keyed: Vec<(usize, u32)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| Some((i, key(l)?)))
+        .collect();
+    let &(last, _) = keyed.last()?;
+
+    let at = match keyed.iter().find(|(_, n)| *n > number) {
+        Some(&(mut i, _)) => {
+            while i > 0 && lines[i - 1].trim_start().starts_with("#[") {
+                i -= 1;
+            }
+            i
+        }
+        None => last + 1,
+    };
+
+    let mut out = lines[..at].to_vec();
+    out.push(line);
+    out.extend_from_slice(&lines[at..]);
+    let mut out = out.join("\n");
+    if source.ends_with('\n') {
+        out.push('\n');
+    }
+    Some(out)
+}
+
+static MOD_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?-u)^pub mod (?:day|year)(\d+);").unwrap());
+static ENTRY_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?-u)^\s*entry!\((\d+), (\d+), ").unwrap());
+
+fn mod_key(line: &str) -> Option<u32> {
+    MOD_REGEX.captures(line)?[1].parse().ok()
+}
+
+fn entry_key(line: &str) -> Option<u32> {
+    let c = ENTRY_REGEX.captures(line)?;
+    Some(c[1].parse::<u32>().ok()? * 100 + c[2].parse::<u32>().ok()?)
+}
+
+/// Adds `line` to the file at `path` unless it is already there.
+/// Returns whether the file changed.
+fn register(path: &Path, key: fn(&str) -> Option<u32>, line: &str) -> Result<bool, Error> {
+    let source = read(path)?;
+    if source.lines().any(|l| l.trim() == line.trim()) {
+        return Ok(false);
+    }
+    let updated = insert_sorted(&source, key, line)
+        .ok_or_else(|| Error::NoInsertionPoint(path.to_path_buf()))?;
+    write(path, &updated)?;
+    Ok(true)
+}
+
+fn find_template(src: &Path, year: u16) -> Result<String, Error> {
+    let own = src.join(format!("year{year}")).join("template.rs");
+    if own.exists() {
+        return read(&own);
+    }
+    let newest = std::fs::read_dir(src)
+        .map_err(|e| Error::Io(src.to_path_buf(), e))?
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let name = e.file_name().into_string().ok()?;
+            let y: u16 = name.strip_prefix("year")?.parse().ok()?;
+            let template = e.path().join("template.rs");
+            template.exists().then_some((y, template))
+        })
+        .max_by_key(|(y, _)| *y)
+        .ok_or(Error::NoTemplate)?;
+    read(&newest.1)
+}
+
+/// Creates `src/yearYYYY/dayN.rs` from the year's template, registers it in `mod.rs`, `lib.rs`
+/// and the solution registry, and creates empty input and example files.
+/// `root` is the crate directory. Returns the files that were created or changed.
+pub fn new_day(root: &Path, year: u16, day: u8) -> Result<Vec<PathBuf>, Error> {
+    let src = root.join("src");
+    let year_dir = src.join(format!("year{year}"));
+    let day_file = year_dir.join(format!("day{day}.rs"));
+    if day_file.exists() {
+        return Err(Error::AlreadyExists(day_file));
+    }
+
+    let template = find_template(&src, year)?;
+    let mut touched = Vec::new();
+
+    let year_template = year_dir.join("template.rs");
+    if !year_template.exists() {
+        write(&year_template, &render_template(&template, year, None))?;
+        touched.push(year_template);
+    }
+    write(&day_file, &render_template(&template, year, Some(day)))?;
+    touched.push(day_file);
+
+    let mod_file = year_dir.join("mod.rs");
+    let day_line = format!("pub mod day{day};");
+    if !mod_file.exists() {
+        write(&mod_file, &format!("{day_line}\n"))?;
+        touched.push(mod_file);
+    } else if register(&mod_file, mod_key, &day_line)? {
+        touched.push(mod_file);
+    }
+
+    let lib_file = src.join("lib.rs");
+    if register(&lib_file, mod_key, &format!("pub mod year{year};"))? {
+        touched.push(lib_file);
+    }
+
+    let registry_file = src.join("registry.rs");
+    let entry_line = format!("    entry!({year}, {day}, year{year}::day{day}),");
+    if register(&registry_file, entry_key, &entry_line)? {
+        touched.push(registry_file);
+    }
+
+    for name in [format!("day{day}.txt"), format!("day{day}_example1.txt")] {
+        let path = root.join("input").join(year.to_string()).join(name);
+        if !path.exists() {
+            write(&path, "")?;
+            touched.push(path);
+        }
+    }
+
+    Ok(touched)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn template_input() {
+        let template = r#"provider::input(2025, n)
+examples::input(2025, n, 1)"#;
+        assert_eq!(
+            r#"provider::input(2026, 3)
+examples::input(2026, 3, 1)"#,
+            render_template(template, 2026, Some(3))
+        );
+        assert_eq!(
+            r#"provider::input(2026, n)
+examples::input(2026, n, 1)"#,
+            render_template(template, 2026, None)
+        );
+    }
+
+    const MOD: &str = "pub mod day1;
+#[rustfmt::skip]
+pub mod day10;
+
+// aoc_lib!{ year = 2022 }
+";
+
+    #[test]
+    pub fn insert_mod_before_attribute() {
+        assert_eq!(
+            "pub mod day1;
+pub mod day2;
+#[rustfmt::skip]
+pub mod day10;
+
+// aoc_lib!{ year = 2022 }
+",
+            insert_sorted(MOD, mod_key, "pub mod day2;").unwrap()
+        );
+        assert_eq!(
+            "pub mod day1;
+#[rustfmt::skip]
+pub mod day10;
+pub mod day15;
+
+// aoc_lib!{ year = 2022 }
+",
+            insert_sorted(MOD, mod_key, "pub mod day15;").unwrap()
+        );
+        assert_eq!(None, insert_sorted("", mod_key, "pub mod day1;"));
+    }
+
+    #[test]
+    pub fn insert_registry_entry() {
+        let source = "pub static SOLUTIONS: &[Entry] = &[
+    entry!(2022, 14, year2022::day14),
+    entry!(2023, 16, year2023::day16),
+    entry!(2025, 1, year2025::day1),
+];
+";
+        assert_eq!(
+            "pub static SOLUTIONS: &[Entry] = &[
+    entry!(2022, 14, year2022::day14),
+    entry!(2023, 2, year2023::day2),
+    entry!(2023, 16, year2023::day16),
+    entry!(2025, 1, year2025::day1),
+];
+",
+            insert_sorted(source, entry_key, "    entry!(2023, 2, year2023::day2),").unwrap()
+        );
+    }
+
+    #[test]
+    pub fn new_day_in_new_year() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let src = root.join("src");
+        write(
+            &src.join("lib.rs"),
+            "pub mod registry;\npub mod year2025;\n",
+        )
+        .unwrap();
+        write(
+            &src.join("registry.rs"),
+            "&[\n    entry!(2025, 1, year2025::day1),\n];\n",
+        )
+        .unwrap();
+        write(
+            &src.join("year2025").join("template.rs"),
+            r#"provider::input(2025, n)"#,
+        )
+        .unwrap();
+
+        let touched = new_day(&root, 2026, 3).unwrap();
+        assert_eq!(7, touched.len());
+        assert_eq!(
+            r#"provider::input(2026, 3)"#,
+            read(&src.join("year2026").join("day3.rs")).unwrap()
+        );
+        assert_eq!(
+            r#"provider::input(2026, n)"#,
+            read(&src.join("year2026").join("template.rs")).unwrap()
+        );
+        assert_eq!(
+            "pub mod day3;\n",
+            read(&src.join("year2026").join("mod.rs")).unwrap()
+        );
+        assert_eq!(
+            "pub mod registry;\npub mod year2025;\npub mod year2026;\n",
+            read(&src.join("lib.rs")).unwrap()
+        );
+        assert!(
+            read(&src.join("registry.rs"))
+                .unwrap()
+                .contains("entry!(2026, 3, year2026::day3),\n];")
+        );
+        assert!(root.join("input/2026/day3.txt").exists());
+        assert!(root.join("input/2026/day3_example1.txt").exists());
+
+        assert!(matches!(
+            new_day(&root, 2026, 3),
+            Err(Error::AlreadyExists(_))
+        ));
+        assert_eq!(5, new_day(&root, 2026, 1).unwrap().len());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
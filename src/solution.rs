@@ -0,0 +1,160 @@
+use std::{
+    fmt::Display,
+    hint::black_box,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use crate::util::ParseError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl FromStr for Part {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    /// Multi-line output such as an image drawn in `#` and `.`
+    Rendering(String),
+}
+
+impl Answer {
+    pub fn is_multiline(&self) -> bool {
+        match self {
+            Answer::Integer(_) => false,
+            Answer::Text(s) | Answer::Rendering(s) => s.contains('\n'),
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(i) => write!(f, "{i}"),
+            Answer::Text(s) | Answer::Rendering(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+macro_rules! answer_from_integer {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(value: $t) -> Self {
+                Answer::Integer(value as i128)
+            }
+        })*
+    };
+}
+
+answer_from_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+/// A day's puzzle: the input is parsed once and both parts are computed from the parsed form.
+pub trait Solution {
+    type Parsed<'a>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError>;
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Answer;
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Answer;
+
+    /// A picture of how the part was solved, for days that can draw one.
+    fn render(_parsed: &Self::Parsed<'_>, _part: Part) -> Option<String> {
+        None
+    }
+}
+
+/// Wall-clock time of one parse and one run of each part.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timing {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+/// Object-safe view of a [`Solution`], so days with different parsed types can share a table.
+pub trait DynSolution: Sync {
+    fn solve(&self, input: &str, part: Part) -> Result<Answer, ParseError>;
+
+    fn solve_both(&self, input: &str) -> Result<[Answer; 2], ParseError>;
+
+    fn solve_timed(&self, input: &str) -> Result<([Answer; 2], Timing), ParseError>;
+
+    fn render(&self, input: &str, part: Part) -> Result<Option<String>, ParseError>;
+}
+
+impl<S: Solution + Sync> DynSolution for S {
+    fn solve(&self, input: &str, part: Part) -> Result<Answer, ParseError> {
+        let parsed = S::parse(input)?;
+        Ok(match part {
+            Part::One => S::part1(&parsed),
+            Part::Two => S::part2(&parsed),
+        })
+    }
+
+    fn solve_both(&self, input: &str) -> Result<[Answer; 2], ParseError> {
+        let parsed = S::parse(input)?;
+        Ok([S::part1(&parsed), S::part2(&parsed)])
+    }
+
+    fn solve_timed(&self, input: &str) -> Result<([Answer; 2], Timing), ParseError> {
+        let start = Instant::now();
+        let parsed = black_box(S::parse(black_box(input))?);
+        let parsed_at = Instant::now();
+        let answer1 = black_box(S::part1(&parsed));
+        let part1_at = Instant::now();
+        let answer2 = black_box(S::part2(&parsed));
+        let part2_at = Instant::now();
+
+        let timing = Timing {
+            parse: parsed_at - start,
+            part1: part1_at - parsed_at,
+            part2: part2_at - part1_at,
+        };
+        Ok(([answer1, answer2], timing))
+    }
+
+    fn render(&self, input: &str, part: Part) -> Result<Option<String>, ParseError> {
+        Ok(S::render(&S::parse(input)?, part))
+    }
+}
@@ -0,0 +1,479 @@
+use std::{fmt::Display, time::Duration};
+
+use itertools::Itertools;
+
+use crate::{
+    answers::{Answers, RecordedAnswer, Source},
+    config::{self, Config},
+    http,
+    solution::{Answer, Part},
+    util::scan,
+};
+
+/// What the server made of a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without saying which way
+    Wrong,
+    /// Another answer was given too recently, with how long is left to wait if the server said
+    Wait(Option<Duration>),
+    /// The part is already solved, or not unlocked yet
+    WrongLevel,
+    /// A reply that is none of the above, as plain text
+    Unknown(String),
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh => write!(f, "wrong, too high"),
+            Outcome::TooLow => write!(f, "wrong, too low"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::Wait(Some(left)) => write!(
+                f,
+                "not checked, an answer was given too recently; wait {}s",
+                left.as_secs()
+            ),
+            Outcome::Wait(None) => write!(
+                f,
+                "not checked, an answer was given too recently; wait and try again"
+            ),
+            Outcome::WrongLevel => write!(
+                f,
+                "not checked, the part is already solved or not unlocked yet"
+            ),
+            Outcome::Unknown(text) => write!(f, "not understood, the server said: {text}"),
+        }
+    }
+}
+
+/// The text of the page's `<main>` element, or the whole page, without tags and with runs of
+/// whitespace collapsed.
+fn page_text(body: &str) -> String {
+    let main = body.split_once("<main>").map_or(body, |(_, rest)| {
+        rest.split_once("</main>").map_or(rest, |(main, _)| main)
+    });
+    let mut text = String::with_capacity(main.len());
+    let mut in_tag = false;
+    for c in main.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().join(" ")
+}
+
+/// The time in "You have 1m 5s left to wait".
+fn wait_time(text: &str) -> Option<Duration> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = start + text[start..].find(" left to wait")?;
+    text[start..end]
+        .split_whitespace()
+        .try_fold(Duration::ZERO, |total, amount| {
+            let seconds = match scan::prefix::<u64>(amount)? {
+                (n, "s") => n,
+                (n, "m") => n * 60,
+                (n, "h") => n * 3600,
+                _ => return None,
+            };
+            Some(total + Duration::from_secs(seconds))
+        })
+}
+
+/// Reads the reply to a submission, as worded by the puzzle site.
+pub fn parse_outcome(body: &str) -> Outcome {
+    let text = page_text(body);
+    if text.contains("That's the right answer") {
+        Outcome::Correct
+    } else if text.contains("answer too recently") {
+        Outcome::Wait(wait_time(&text))
+    } else if text.contains("solving the right level") {
+        Outcome::WrongLevel
+    } else if text.contains("not the right answer") {
+        if text.contains("too high") {
+            Outcome::TooHigh
+        } else if text.contains("too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Wrong
+        }
+    } else {
+        Outcome::Unknown(text)
+    }
+}
+
+/// Why an answer was not sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    /// A picture, which cannot be typed in as it is
+    Rendering,
+    /// The server already accepted an answer to the part
+    Solved(RecordedAnswer),
+    KnownWrong(RecordedAnswer),
+    /// At least as high as an answer that was too high
+    TooHigh {
+        answer: i64,
+        limit: i64,
+    },
+    /// At most as low as an answer that was too low
+    TooLow {
+        answer: i64,
+        limit: i64,
+    },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::Rendering => write!(f, "the answer is a picture, not something to submit"),
+            Refusal::Solved(recorded) => write!(f, "the part is already solved with {recorded}"),
+            Refusal::KnownWrong(answer) => write!(f, "{answer} was already rejected"),
+            Refusal::TooHigh { answer, limit } => {
+                write!(f, "{answer} is too high, since {limit} already was")
+            }
+            Refusal::TooLow { answer, limit } => {
+                write!(f, "{answer} is too low, since {limit} already was")
+            }
+        }
+    }
+}
+
+/// The answer as it would be sent, or why sending it would be pointless.
+pub fn check(
+    answers: &Answers,
+    day: u8,
+    part: Part,
+    answer: &Answer,
+) -> Result<RecordedAnswer, Refusal> {
+    if matches!(answer, Answer::Rendering(_)) || answer.is_multiline() {
+        return Err(Refusal::Rendering);
+    }
+    let answer = RecordedAnswer::from(answer);
+    if let Some(recorded) = answers.confirmed(day, part) {
+        return Err(Refusal::Solved(recorded.clone()));
+    }
+    let Some(rejected) = answers.rejected(day, part) else {
+        return Ok(answer);
+    };
+    if rejected.wrong.contains(&answer) {
+        return Err(Refusal::KnownWrong(answer));
+    }
+    if let RecordedAnswer::Integer(n) = answer {
+        if let Some(limit) = rejected.too_high.filter(|&limit| n >= limit) {
+            return Err(Refusal::TooHigh { answer: n, limit });
+        }
+        if let Some(limit) = rejected.too_low.filter(|&limit| n <= limit) {
+            return Err(Refusal::TooLow { answer: n, limit });
+        }
+    }
+    Ok(answer)
+}
+
+/// Notes what `outcome` says about `answer` in `answers`. Returns whether anything was learned.
+pub fn record(
+    answers: &mut Answers,
+    day: u8,
+    part: Part,
+    answer: &RecordedAnswer,
+    outcome: &Outcome,
+) -> bool {
+    let rejected = match outcome {
+        Outcome::Correct => {
+            let day = answers.day_mut(day);
+            *day.get_mut(part) = Some(answer.clone());
+            *day.source_mut(part) = Source::Server;
+            return true;
+        }
+        Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong => {
+            answers.day_mut(day).rejected_mut(part)
+        }
+        Outcome::Wait(_) | Outcome::WrongLevel | Outcome::Unknown(_) => return false,
+    };
+    if let RecordedAnswer::Integer(n) = *answer {
+        match outcome {
+            Outcome::TooHigh => {
+                rejected.too_high = Some(rejected.too_high.map_or(n, |limit| limit.min(n)))
+            }
+            Outcome::TooLow => {
+                rejected.too_low = Some(rejected.too_low.map_or(n, |limit| limit.max(n)))
+            }
+            _ => {}
+        }
+    }
+    if !rejected.wrong.contains(answer) {
+        rejected.wrong.push(answer.clone());
+    }
+    true
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Config(config::Error),
+    /// Nowhere to submit to
+    NoServer,
+    Http(http::Error),
+    Status(u16),
+    Refused(Refusal),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Config(e) => write!(f, "{e}"),
+            Error::NoServer => write!(
+                f,
+                "no `submit_url` or `url` is set in {} to submit to",
+                config::CONFIG_FILE
+            ),
+            Error::Http(e) => write!(f, "could not submit: {e}"),
+            Error::Status(status) => {
+                write!(f, "could not submit: the server answered HTTP {status}")
+            }
+            Error::Refused(refusal) => write!(f, "not submitting: {refusal}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<config::Error> for Error {
+    fn from(e: config::Error) -> Self {
+        Error::Config(e)
+    }
+}
+
+impl From<http::Error> for Error {
+    fn from(e: http::Error) -> Self {
+        Error::Http(e)
+    }
+}
+
+/// `text` encoded as an `application/x-www-form-urlencoded` value.
+fn form_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for b in text.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(b as char)
+            }
+            b' ' => encoded.push('+'),
+            b => encoded.push_str(&format!("%{b:02X}")),
+        }
+    }
+    encoded
+}
+
+/// Posts answers to a server answering `POST <base_url>/<year>/day/<day>/answer` like the
+/// puzzle site.
+#[derive(Debug, Clone)]
+pub struct Client {
+    pub base_url: String,
+    pub session: Option<String>,
+}
+
+impl Client {
+    pub fn from_config(config: &Config) -> Result<Self, Error> {
+        Ok(Self {
+            base_url: config.submit_url().ok_or(Error::NoServer)?.to_string(),
+            session: config.session()?,
+        })
+    }
+
+    /// Sends `answer` as it is and reads the reply.
+    pub fn post(
+        &self,
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &RecordedAnswer,
+    ) -> Result<Outcome, Error> {
+        let url = format!(
+            "{}/{year}/day/{day}/answer",
+            self.base_url.trim_end_matches('/')
+        );
+        let cookie = self.session.as_ref().map(|s| format!("session={s}"));
+        let mut headers = vec![("Content-Type", "application/x-www-form-urlencoded")];
+        headers.extend(cookie.iter().map(|c| ("Cookie", c.as_str())));
+        let body = format!("level={part}&answer={}", form_encode(&answer.to_string()));
+
+        let response = http::post(&url, &headers, &body)?;
+        if !response.is_success() {
+            return Err(Error::Status(response.status));
+        }
+        Ok(parse_outcome(&response.body))
+    }
+
+    /// Sends `answer` unless [`check`] refuses it, and records the outcome in `answers`.
+    pub fn submit(
+        &self,
+        answers: &mut Answers,
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &Answer,
+    ) -> Result<Outcome, Error> {
+        let answer = check(answers, day, part, answer).map_err(Error::Refused)?;
+        let outcome = self.post(year, day, part, &answer)?;
+        record(answers, day, part, &answer, &outcome);
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(message: &str) -> String {
+        format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\n\r\n<!DOCTYPE html>\n<html><body>\
+             <header><h1>Advent of Code</h1></header>\n<main>\n<article><p>{message}</p>\
+             </article>\n</main>\n</body></html>\n"
+        )
+    }
+
+    #[test]
+    pub fn outcomes() {
+        let outcome = |message| parse_outcome(&page(message));
+        assert_eq!(
+            Outcome::Correct,
+            outcome("That's the right answer!  You are <span>one gold star</span> closer.")
+        );
+        assert_eq!(
+            Outcome::TooHigh,
+            outcome("That's not the right answer; your answer is too high.  If you're stuck...")
+        );
+        assert_eq!(
+            Outcome::TooLow,
+            outcome("That's not the right answer; your answer is too low.")
+        );
+        assert_eq!(
+            Outcome::Wrong,
+            outcome("That's not the right answer.  If you're stuck, make sure...")
+        );
+        assert_eq!(
+            Outcome::Wait(Some(Duration::from_secs(65))),
+            outcome(
+                "You gave an answer too recently; you have to wait after submitting an answer \
+                 before trying again.  You have 1m 5s left to wait."
+            )
+        );
+        assert_eq!(
+            Outcome::WrongLevel,
+            outcome("You don't seem to be solving the right level.  Did you already complete it?")
+        );
+        assert_eq!(
+            Outcome::Unknown("Puzzle inputs differ by user.".to_string()),
+            outcome("Puzzle inputs <em>differ</em> by user.")
+        );
+    }
+
+    #[test]
+    pub fn encoding() {
+        assert_eq!("CMZ", form_encode("CMZ"));
+        assert_eq!("-12", form_encode("-12"));
+        assert_eq!("a+b%26c%3D", form_encode("a b&c="));
+    }
+
+    #[test]
+    pub fn submitting() {
+        let mut answers = Answers::default();
+        // saved by `verify --record`, so not known to be right
+        answers.record(1, Part::One, &450usize.into());
+        assert_eq!(
+            Ok(RecordedAnswer::Integer(450)),
+            check(&answers, 1, Part::One, &450usize.into())
+        );
+        let (url, server) = http::serve_once(&page(
+            "That's not the right answer; your answer is too high.",
+        ));
+        let client = Client {
+            base_url: url,
+            session: Some("abc".to_string()),
+        };
+
+        let outcome = client.submit(&mut answers, 2022, 1, Part::One, &500usize.into());
+        assert_eq!(Outcome::TooHigh, outcome.unwrap());
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2022/day/1/answer HTTP/1.0\r\n"));
+        assert!(request.contains("\r\nCookie: session=abc\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=1&answer=500"));
+        let rejected = answers.rejected(1, Part::One).unwrap();
+        assert_eq!(vec![RecordedAnswer::Integer(500)], rejected.wrong);
+        assert_eq!(Some(500), rejected.too_high);
+
+        // refused before anything is sent
+        assert!(matches!(
+            client.submit(&mut answers, 2022, 1, Part::One, &500usize.into()),
+            Err(Error::Refused(Refusal::KnownWrong(
+                RecordedAnswer::Integer(500)
+            )))
+        ));
+        assert!(matches!(
+            client.submit(&mut answers, 2022, 1, Part::One, &600usize.into()),
+            Err(Error::Refused(Refusal::TooHigh {
+                answer: 600,
+                limit: 500
+            }))
+        ));
+        assert!(matches!(
+            client.submit(
+                &mut answers,
+                2022,
+                1,
+                Part::Two,
+                &Answer::Rendering("#.\n.#".into())
+            ),
+            Err(Error::Refused(Refusal::Rendering))
+        ));
+
+        let (url, server) = http::serve_once(&page("You gave an answer too recently."));
+        let client = Client {
+            base_url: url,
+            session: None,
+        };
+        let outcome = client.submit(&mut answers, 2022, 1, Part::One, &450usize.into());
+        assert_eq!(Outcome::Wait(None), outcome.unwrap());
+        server.join().unwrap();
+        assert_eq!(None, answers.confirmed(1, Part::One));
+
+        let (url, server) = http::serve_once(&page("That's the right answer!"));
+        let client = Client {
+            base_url: url,
+            session: None,
+        };
+        let outcome = client.submit(&mut answers, 2022, 1, Part::One, &450usize.into());
+        assert_eq!(Outcome::Correct, outcome.unwrap());
+        server.join().unwrap();
+        assert_eq!(
+            Some(&RecordedAnswer::Integer(450)),
+            answers.confirmed(1, Part::One)
+        );
+        assert!(matches!(
+            client.submit(&mut answers, 2022, 1, Part::One, &450usize.into()),
+            Err(Error::Refused(Refusal::Solved(RecordedAnswer::Integer(
+                450
+            ))))
+        ));
+
+        let toml = answers.to_toml().unwrap();
+        assert_eq!(answers, Answers::from_toml(&toml).unwrap());
+
+        let (url, server) = http::serve_once("HTTP/1.1 500 Internal Server Error\r\n\r\n");
+        let client = Client {
+            base_url: url,
+            session: None,
+        };
+        assert!(matches!(
+            client.submit(&mut answers, 2022, 2, Part::One, &"CMZ".into()),
+            Err(Error::Status(500))
+        ));
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,235 @@
+use super::parse::ParseError;
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+/// A point or displacement on the integer plane. `y` grows downwards, matching grid rows.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize,
+}
+
+pub type Vec2 = Point;
+
+/// The 4 orthogonal unit steps: up, right, down, left.
+pub const NEIGHBORS_4: [Point; 4] = [
+    Point::new(0, -1),
+    Point::new(1, 0),
+    Point::new(0, 1),
+    Point::new(-1, 0),
+];
+
+/// The 8 orthogonal and diagonal unit steps, in row-major order.
+pub const NEIGHBORS_8: [Point; 8] = [
+    Point::new(-1, -1),
+    Point::new(0, -1),
+    Point::new(1, -1),
+    Point::new(-1, 0),
+    Point::new(1, 0),
+    Point::new(-1, 1),
+    Point::new(0, 1),
+    Point::new(1, 1),
+];
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan(self, other: Point) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    pub fn chebyshev(self, other: Point) -> usize {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// Each coordinate clamped to -1, 0 or 1: one step towards this displacement.
+    pub fn signum(self) -> Point {
+        Point::new(self.x.signum(), self.y.signum())
+    }
+
+    pub fn neighbors4(self) -> impl Iterator<Item = Point> {
+        NEIGHBORS_4.into_iter().map(move |d| self + d)
+    }
+
+    pub fn neighbors8(self) -> impl Iterator<Item = Point> {
+        NEIGHBORS_8.into_iter().map(move |d| self + d)
+    }
+
+    /// The point as `(x, y)` grid coordinates, if neither is negative.
+    pub fn to_unsigned(self) -> Option<(usize, usize)> {
+        Some((self.x.try_into().ok()?, self.y.try_into().ok()?))
+    }
+}
+
+impl From<(isize, isize)> for Point {
+    fn from((x, y): (isize, isize)) -> Self {
+        Point::new(x, y)
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((x, y): (usize, usize)) -> Self {
+        Point::new(x as isize, y as isize)
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Self::Output {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Self::Output {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Point) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Point) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Self::Output {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<isize> for Point {
+    type Output = Point;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Point::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Clockwise from up.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// The unit step in this direction.
+    pub fn offset(self) -> Point {
+        NEIGHBORS_4[self as usize]
+    }
+
+    pub fn turn_right(self) -> Direction {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Direction {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Direction {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Direction::Up | Direction::Down)
+    }
+}
+
+/// Parses `U`/`D`/`L`/`R`, or the compass points `N`/`E`/`S`/`W`.
+impl FromStr for Direction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "U" | "N" => Ok(Direction::Up),
+            "R" | "E" => Ok(Direction::Right),
+            "D" | "S" => Ok(Direction::Down),
+            "L" | "W" => Ok(Direction::Left),
+            _ => Err(ParseError::new(s, "a direction (U, D, L, R, N, E, S or W)")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn arithmetic() {
+        let a = Point::new(1, -2);
+        let b = Point::new(4, 2);
+        assert_eq!(Point::new(5, 0), a + b);
+        assert_eq!(Point::new(3, 4), b - a);
+        assert_eq!(Point::new(-1, 2), -a);
+        assert_eq!(Point::new(3, -6), a * 3);
+        assert_eq!(7, a.manhattan(b));
+        assert_eq!(4, a.chebyshev(b));
+        assert_eq!(Point::new(1, 1), (b - a).signum());
+        assert_eq!(None, a.to_unsigned());
+        assert_eq!(Some((4, 2)), b.to_unsigned());
+    }
+
+    #[test]
+    pub fn turning() {
+        use Direction as D;
+        assert_eq!(D::Right, D::Up.turn_right());
+        assert_eq!(D::Left, D::Up.turn_left());
+        assert_eq!(D::Up, D::Left.turn_right());
+        assert_eq!(D::Down, D::Up.reverse());
+        for d in D::ALL {
+            assert_eq!(d, d.turn_left().turn_right());
+            assert_eq!(Point::ORIGIN, d.offset() + d.reverse().offset());
+        }
+    }
+
+    #[test]
+    pub fn parsing() {
+        assert_eq!(Ok(Direction::Up), "U".parse());
+        assert_eq!(Ok(Direction::Up), "N".parse());
+        assert_eq!(Ok(Direction::Left), "W".parse());
+        assert_eq!("X", "X".parse::<Direction>().unwrap_err().text);
+    }
+
+    #[test]
+    pub fn neighborhoods() {
+        let p = Point::new(2, 3);
+        assert_eq!(4, p.neighbors4().count());
+        assert!(p.neighbors4().all(|n| n.manhattan(p) == 1));
+        assert_eq!(8, p.neighbors8().count());
+        assert!(p.neighbors8().all(|n| n.chebyshev(p) == 1));
+    }
+}
@@ -0,0 +1,383 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use super::geometry::{NEIGHBORS_4, NEIGHBORS_8, Point};
+
+/// A rectangular grid stored row-major. Positions are `(x, y)` with `x` the column and `y` the
+/// row, both counted from the top left.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    /// A row's length differs from the first row's
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {row} has {found} cells, expected {expected} like the first row"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    pub fn transpose(&self) -> Self {
+        Self {
+            cells: self.columns().flatten().cloned().collect(),
+            width: self.height,
+            height: self.width,
+        }
+    }
+
+    /// Rotates a quarter turn clockwise, so the left column becomes the top row.
+    pub fn rotate_clockwise(&self) -> Self {
+        Self {
+            cells: (0..self.width)
+                .flat_map(|x| (0..self.height).rev().map(move |y| self[(x, y)].clone()))
+                .collect(),
+            width: self.height,
+            height: self.width,
+        }
+    }
+
+    /// Rotates a quarter turn counterclockwise, so the right column becomes the top row.
+    pub fn rotate_counterclockwise(&self) -> Self {
+        Self {
+            cells: (0..self.width)
+                .rev()
+                .flat_map(|x| (0..self.height).map(move |y| self[(x, y)].clone()))
+                .collect(),
+            width: self.height,
+            height: self.width,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from row-major cells.
+    ///
+    /// # Panics
+    /// If `cells.len()` is not a multiple of `width`.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        assert_eq!(
+            width * height,
+            cells.len(),
+            "cells do not fill {width} columns"
+        );
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    pub fn from_rows<R: IntoIterator<Item = T>>(
+        rows: impl IntoIterator<Item = R>,
+    ) -> Result<Self, GridError> {
+        let mut cells = Vec::new();
+        let mut width = 0;
+        let mut height = 0;
+        for (y, row) in rows.into_iter().enumerate() {
+            let before = cells.len();
+            cells.extend(row);
+            let found = cells.len() - before;
+            if y == 0 {
+                width = found;
+            } else if found != width {
+                return Err(GridError::Ragged {
+                    row: y,
+                    expected: width,
+                    found,
+                });
+            }
+            height += 1;
+        }
+        Ok(Self {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    /// Parses a character map, one row per non-empty line.
+    pub fn parse(s: &str, mut f: impl FnMut(char) -> T) -> Result<Self, GridError> {
+        Self::from_rows(
+            s.lines()
+                .filter(|l| !l.is_empty())
+                .map(|l| l.chars().map(&mut f).collect::<Vec<_>>()),
+        )
+    }
+
+    /// Parses a map of ASCII bytes, one row per non-empty line.
+    pub fn parse_bytes(s: &str, mut f: impl FnMut(u8) -> T) -> Result<Self, GridError> {
+        Self::from_rows(
+            s.lines()
+                .filter(|l| !l.is_empty())
+                .map(|l| l.bytes().map(&mut f).collect::<Vec<_>>()),
+        )
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, (x, y): (usize, usize)) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// Index of a position into the row-major cells.
+    pub fn index_of(&self, (x, y): (usize, usize)) -> usize {
+        y * self.width + x
+    }
+
+    pub fn position_of(&self, index: usize) -> (usize, usize) {
+        (index % self.width, index / self.width)
+    }
+
+    pub fn get(&self, pos: (usize, usize)) -> Option<&T> {
+        self.contains(pos).then(|| &self.cells[self.index_of(pos)])
+    }
+
+    pub fn get_mut(&mut self, pos: (usize, usize)) -> Option<&mut T> {
+        if self.contains(pos) {
+            let i = self.index_of(pos);
+            Some(&mut self.cells[i])
+        } else {
+            None
+        }
+    }
+
+    /// Moves from `pos` by `offset`, if that stays inside the grid.
+    pub fn offset(&self, (x, y): (usize, usize), offset: Point) -> Option<(usize, usize)> {
+        let next = (
+            x.checked_add_signed(offset.x)?,
+            y.checked_add_signed(offset.y)?,
+        );
+        self.contains(next).then_some(next)
+    }
+
+    /// Every position, in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    /// Every position with its cell, in row-major order.
+    pub fn enumerate(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Orthogonal neighbors of `pos` that are inside the grid.
+    pub fn neighbors4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        NEIGHBORS_4.iter().filter_map(move |&o| self.offset(pos, o))
+    }
+
+    /// Orthogonal and diagonal neighbors of `pos` that are inside the grid.
+    pub fn neighbors8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        NEIGHBORS_8.iter().filter_map(move |&o| self.offset(pos, o))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// The cells of column `x`, top to bottom.
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        assert!(x < self.width, "column {x} out of bounds");
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Draws the grid with one character per cell.
+    pub fn render(&self, mut f: impl FnMut(&T) -> char) -> String {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            out.extend(row.iter().map(&mut f));
+            out.push('\n');
+        }
+        out.pop();
+        out
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &Self::Output {
+        assert!(
+            self.contains(pos),
+            "{pos:?} out of bounds for {}x{} grid",
+            self.width,
+            self.height
+        );
+        &self.cells[self.index_of(pos)]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, pos: (usize, usize)) -> &mut Self::Output {
+        assert!(
+            self.contains(pos),
+            "{pos:?} out of bounds for {}x{} grid",
+            self.width,
+            self.height
+        );
+        let i = self.index_of(pos);
+        &mut self.cells[i]
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r"
+abc
+def";
+
+    fn example() -> Grid<char> {
+        Grid::parse(EXAMPLE, |c| c).unwrap()
+    }
+
+    #[test]
+    pub fn parsing() {
+        let grid = example();
+        assert_eq!(3, grid.width());
+        assert_eq!(2, grid.height());
+        assert_eq!('a', grid[(0, 0)]);
+        assert_eq!('f', grid[(2, 1)]);
+        assert_eq!(None, grid.get((3, 0)));
+        assert_eq!(EXAMPLE.trim(), grid.to_string());
+
+        assert_eq!(
+            Err(GridError::Ragged {
+                row: 1,
+                expected: 3,
+                found: 2
+            }),
+            Grid::parse("abc\nde", |c| c)
+        );
+    }
+
+    #[test]
+    pub fn neighbors() {
+        let grid = example();
+        assert_eq!(
+            vec![(1, 0), (0, 1)],
+            grid.neighbors4((0, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(0, 0), (1, 0), (2, 0), (0, 1), (2, 1)],
+            grid.neighbors8((1, 1)).collect::<Vec<_>>()
+        );
+        assert_eq!(3, grid.neighbors8((2, 1)).count());
+    }
+
+    #[test]
+    pub fn views() {
+        let grid = example();
+        assert_eq!(&['d', 'e', 'f'], grid.row(1));
+        assert_eq!(vec![&'b', &'e'], grid.column(1).collect::<Vec<_>>());
+        assert_eq!(
+            vec![(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)],
+            grid.positions().collect::<Vec<_>>()
+        );
+        assert_eq!((2, 1), grid.position_of(grid.index_of((2, 1))));
+    }
+
+    #[test]
+    pub fn transforms() {
+        let grid = example();
+        assert_eq!("ad\nbe\ncf", grid.transpose().to_string());
+        assert_eq!("da\neb\nfc", grid.rotate_clockwise().to_string());
+        assert_eq!("cf\nbe\nad", grid.rotate_counterclockwise().to_string());
+        assert_eq!(grid, grid.rotate_clockwise().rotate_counterclockwise());
+        assert_eq!(
+            "#..\n...",
+            grid.render(|&c| if c == 'a' { '#' } else { '.' })
+        );
+    }
+}
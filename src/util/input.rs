@@ -0,0 +1,41 @@
+use std::{io, path::Path};
+
+/// `raw` without a byte order mark, carriage returns, trailing whitespace on any line, or blank
+/// lines at the start and end, ending in a single newline unless empty.
+/// Leading whitespace is kept, as some drawings depend on it.
+pub fn normalize(raw: &str) -> String {
+    let raw = raw.strip_prefix('\u{feff}').unwrap_or(raw);
+    let mut normalized = String::with_capacity(raw.len());
+    for line in raw.lines().map(str::trim_end).skip_while(|l| l.is_empty()) {
+        normalized.push_str(line);
+        normalized.push('\n');
+    }
+    let end = normalized.trim_end().len();
+    normalized.truncate(end);
+    if !normalized.is_empty() {
+        normalized.push('\n');
+    }
+    normalized
+}
+
+/// Reads the input file at `path` and normalizes it.
+pub fn read(path: &Path) -> io::Result<String> {
+    std::fs::read_to_string(path).map(|raw| normalize(&raw))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn normalizing() {
+        assert_eq!("a\n\nb\n", normalize("a\n\nb\n"));
+        assert_eq!("a\n\nb\n", normalize("\u{feff}a\r\n\r\nb\r\n"));
+        assert_eq!("a\n\nb\n", normalize("\n  \na \t\n \nb"));
+        assert_eq!(
+            "    [D]\n[N] [C]\n",
+            normalize("\n    [D]    \n[N] [C]\n\n\n")
+        );
+        assert_eq!("", normalize("\r\n \n"));
+    }
+}
@@ -0,0 +1,10 @@
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod parse;
+pub mod scan;
+pub mod search;
+
+pub use geometry::{Direction, Point};
+pub use grid::Grid;
+pub use parse::ParseError;
@@ -0,0 +1,240 @@
+use std::{fmt::Display, str::FromStr};
+
+use super::grid::{Grid, GridError};
+
+/// Where and why an input could not be parsed. Lines and columns count from 1, columns in
+/// characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    /// The text that could not be parsed
+    pub text: String,
+    /// What should have been there instead
+    pub expected: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found {:?}",
+            self.line, self.column, self.expected, self.text
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Line and column at which `part` starts, if it is a slice of `whole`.
+fn location(whole: &str, part: &str) -> Option<(usize, usize)> {
+    let offset = (part.as_ptr() as usize).checked_sub(whole.as_ptr() as usize)?;
+    if offset + part.len() > whole.len() {
+        return None;
+    }
+    let before = &whole[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    Some((line, before[line_start..].chars().count() + 1))
+}
+
+impl ParseError {
+    /// An error about `text`, located at the start of the input being parsed.
+    pub fn new(text: &str, expected: impl Into<String>) -> Self {
+        Self {
+            line: 1,
+            column: 1,
+            text: text.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// An error about `part`, located where it appears in `whole`.
+    pub fn at(whole: &str, part: &str, expected: impl Into<String>) -> Self {
+        Self::new(part, expected).within(whole, part)
+    }
+
+    /// Moves an error found while parsing `part` on its own to where `part` appears in `whole`.
+    pub fn within(mut self, whole: &str, part: &str) -> Self {
+        if let Some((line, column)) = location(whole, part) {
+            if self.line == 1 {
+                self.column += column - 1;
+            }
+            self.line += line - 1;
+        }
+        self
+    }
+
+    /// Turns a grid's ragged row into an error at that row of `input`.
+    pub fn from_grid(input: &str, error: GridError) -> Self {
+        match error {
+            GridError::Ragged { row, expected, .. } => {
+                let text = split_lines(input).nth(row).unwrap_or("");
+                Self::at(input, text, format!("a row of {expected} cells"))
+            }
+        }
+    }
+}
+
+/// Parses `s` as a number, describing it as `expected` on failure.
+pub fn number<T: FromStr>(s: &str, expected: &str) -> Result<T, ParseError> {
+    s.parse().map_err(|_| ParseError::new(s, expected))
+}
+
+/// The lines of `input` that are not blank, without trailing whitespace.
+pub fn split_lines(input: &str) -> impl Iterator<Item = &str> {
+    input.lines().map(str::trim_end).filter(|l| !l.is_empty())
+}
+
+/// Blocks of consecutive non-blank lines of `input`, separated by one or more blank lines.
+pub fn split_paragraphs(input: &str) -> impl Iterator<Item = &str> {
+    let mut rest = input;
+    std::iter::from_fn(move || {
+        let mut end = 0;
+        let mut start = None;
+        for line in rest.split_inclusive('\n') {
+            let blank = line.trim().is_empty();
+            match start {
+                None if blank => {}
+                None => start = Some(end),
+                Some(_) if blank => break,
+                Some(_) => {}
+            }
+            end += line.len();
+        }
+        let paragraph = rest[start?..end].trim_end();
+        rest = &rest[end..];
+        Some(paragraph)
+    })
+}
+
+/// Parses each non-blank line of `input` with `f`, locating errors within `input`.
+pub fn lines<T>(
+    input: &str,
+    mut f: impl FnMut(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    split_lines(input)
+        .map(|l| f(l).map_err(|e| e.within(input, l)))
+        .collect()
+}
+
+/// Parses each paragraph of `input` with `f`, locating errors within `input`.
+pub fn paragraphs<T>(
+    input: &str,
+    mut f: impl FnMut(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    split_paragraphs(input)
+        .map(|p| f(p).map_err(|e| e.within(input, p)))
+        .collect()
+}
+
+/// Parses every whitespace-separated word of `input` as a number.
+pub fn numbers<T: FromStr>(input: &str, expected: &str) -> Result<Vec<T>, ParseError> {
+    input
+        .split_whitespace()
+        .map(|n| number(n, expected).map_err(|e| e.within(input, n)))
+        .collect()
+}
+
+/// Parses a character map, one row per non-empty line, with `f` giving each character's cell or
+/// `None` if it is not `expected`.
+pub fn grid<T>(
+    input: &str,
+    expected: &str,
+    mut f: impl FnMut(char) -> Option<T>,
+) -> Result<Grid<T>, ParseError> {
+    let rows = lines(input, |line| {
+        line.char_indices()
+            .map(|(i, c)| {
+                f(c).ok_or_else(|| ParseError::at(line, &line[i..i + c.len_utf8()], expected))
+            })
+            .collect::<Result<Vec<_>, _>>()
+    })?;
+    Grid::from_rows(rows).map_err(|e| ParseError::from_grid(input, e))
+}
+
+/// Splits `s` at the first `delimiter`, or fails expecting `expected`.
+pub fn split_once<'a>(
+    s: &'a str,
+    delimiter: &str,
+    expected: &str,
+) -> Result<(&'a str, &'a str), ParseError> {
+    s.split_once(delimiter)
+        .ok_or_else(|| ParseError::new(s, expected))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn locating() {
+        let input = "1 2\n3 x\n";
+        let error = lines(input, |l| {
+            l.split(' ')
+                .map(|n| number::<u32>(n, "a number").map_err(|e| e.within(l, n)))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .unwrap_err();
+        assert_eq!(
+            ParseError {
+                line: 2,
+                column: 3,
+                text: "x".to_string(),
+                expected: "a number".to_string()
+            },
+            error
+        );
+        assert_eq!(
+            "line 2, column 3: expected a number, found \"x\"",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    pub fn splitting() {
+        let input = "\n1 2\n3\t\n \n\n4\n\n";
+        assert_eq!(
+            vec!["1 2", "3", "4"],
+            split_lines(input).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec!["1 2\n3", "4"],
+            split_paragraphs(input).collect::<Vec<_>>()
+        );
+        assert_eq!(None, split_paragraphs(" \n").next());
+
+        let sums = paragraphs(input, |p| Ok(numbers::<u32>(p, "a number")?.iter().sum()));
+        assert_eq!(Ok(vec![6, 4]), sums);
+        let error = paragraphs("1\n\n2 x", |p| numbers::<u32>(p, "a number")).unwrap_err();
+        assert_eq!((3, 3, "x"), (error.line, error.column, error.text.as_str()));
+    }
+
+    #[test]
+    pub fn unrelated_text() {
+        let error = ParseError::at("abc", "x", "y");
+        assert_eq!((1, 1), (error.line, error.column));
+    }
+
+    #[test]
+    pub fn grids() {
+        let digit = |c: char| c.to_digit(10);
+        assert_eq!(3, grid("12\n34\n56", "a digit", digit).unwrap().height());
+
+        let error = grid("\nab\nabc\n", "a letter", Some).unwrap_err();
+        assert_eq!(
+            (3, 1, "abc"),
+            (error.line, error.column, error.text.as_str())
+        );
+
+        let error = grid("ab\n  \nabc\n", "a letter", Some).unwrap_err();
+        assert_eq!(
+            (3, 1, "abc"),
+            (error.line, error.column, error.text.as_str())
+        );
+
+        let error = grid("12\n3x", "a digit", digit).unwrap_err();
+        assert_eq!((2, 2, "x"), (error.line, error.column, error.text.as_str()));
+        assert_eq!("a digit", error.expected);
+    }
+}
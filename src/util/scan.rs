@@ -0,0 +1,199 @@
+use std::marker::PhantomData;
+
+/// An integer type that [`integers`] can pull out of text.
+pub trait Integer: Copy {
+    const ZERO: Self;
+    /// Whether a `-` before the digits makes the number negative.
+    const SIGNED: bool;
+
+    /// `self` with `digit` appended, moving away from zero in the direction given by `negative`,
+    /// or `None` on overflow.
+    fn push_digit(self, digit: u8, negative: bool) -> Option<Self>;
+}
+
+macro_rules! unsigned {
+    ($($t:ty),*) => {$(
+        impl Integer for $t {
+            const ZERO: Self = 0;
+            const SIGNED: bool = false;
+
+            fn push_digit(self, digit: u8, _negative: bool) -> Option<Self> {
+                self.checked_mul(10)?.checked_add(digit as Self)
+            }
+        }
+    )*};
+}
+
+macro_rules! signed {
+    ($($t:ty),*) => {$(
+        impl Integer for $t {
+            const ZERO: Self = 0;
+            const SIGNED: bool = true;
+
+            fn push_digit(self, digit: u8, negative: bool) -> Option<Self> {
+                let shifted = self.checked_mul(10)?;
+                if negative {
+                    shifted.checked_sub(digit as Self)
+                } else {
+                    shifted.checked_add(digit as Self)
+                }
+            }
+        }
+    )*};
+}
+
+unsigned!(u8, u16, u32, u64, u128, usize);
+signed!(i8, i16, i32, i64, i128, isize);
+
+/// The integers in some text, in order. See [`integers`].
+#[derive(Debug, Clone)]
+pub struct Integers<'a, T> {
+    bytes: &'a [u8],
+    pos: usize,
+    integer: PhantomData<T>,
+}
+
+impl<T: Integer> Iterator for Integers<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        loop {
+            let start = self.pos + self.bytes[self.pos..].iter().position(u8::is_ascii_digit)?;
+            let negative = T::SIGNED
+                && start > 0
+                && self.bytes[start - 1] == b'-'
+                && (start < 2 || !self.bytes[start - 2].is_ascii_digit());
+
+            let mut value = Some(T::ZERO);
+            self.pos = start;
+            while let Some(&b) = self.bytes.get(self.pos).filter(|b| b.is_ascii_digit()) {
+                value = value.and_then(|v| v.push_digit(b - b'0', negative));
+                self.pos += 1;
+            }
+            if value.is_some() {
+                return value;
+            }
+        }
+    }
+}
+
+/// Every run of ASCII digits in `text` as a `T`, skipping everything in between and any number
+/// too large for `T`. For signed `T`, a `-` directly before the digits makes the number negative
+/// unless it follows another digit, so `3-5` is 3 and 5 while `x=-5` is -5.
+pub fn integers<T: Integer>(text: &(impl AsRef<[u8]> + ?Sized)) -> Integers<'_, T> {
+    Integers {
+        bytes: text.as_ref(),
+        pos: 0,
+        integer: PhantomData,
+    }
+}
+
+/// The first `N` integers in `text`, or `None` if there are fewer.
+pub fn array<T: Integer, const N: usize>(text: &(impl AsRef<[u8]> + ?Sized)) -> Option<[T; N]> {
+    let mut integers = integers(text);
+    let mut array = [T::ZERO; N];
+    for slot in array.iter_mut() {
+        *slot = integers.next()?;
+    }
+    Some(array)
+}
+
+/// The integer at the very start of `text` and the text after it, or `None` if `text` does not
+/// start with one or it is too large for `T`.
+pub fn prefix<T: Integer>(text: &str) -> Option<(T, &str)> {
+    let negative = T::SIGNED && text.starts_with('-');
+    let start = negative as usize;
+    let digits = text.as_bytes()[start..]
+        .iter()
+        .take_while(|b| b.is_ascii_digit())
+        .count();
+    if digits == 0 {
+        return None;
+    }
+    let end = start + digits;
+    let value = text.as_bytes()[start..end]
+        .iter()
+        .try_fold(T::ZERO, |v, &b| v.push_digit(b - b'0', negative))?;
+    Some((value, &text[end..]))
+}
+
+/// The `N` integers in `text` when it is exactly `literals[0]`, an integer, `literals[1]`, …, an
+/// integer, `literals[N]`. `None` if anything else is there, such as junk between the numbers, more
+/// numbers than `N` or a number too large for `T`.
+// Inlined so callers' literals are known when matching them, which makes it several times faster.
+#[inline]
+pub fn fields<T: Integer, const N: usize>(text: &str, literals: &[&str]) -> Option<[T; N]> {
+    assert_eq!(N + 1, literals.len(), "one more literal than integers");
+    let mut rest = text.strip_prefix(literals[0])?;
+    let mut array = [T::ZERO; N];
+    for (slot, literal) in array.iter_mut().zip(&literals[1..]) {
+        let (value, after) = prefix(rest)?;
+        *slot = value;
+        rest = after.strip_prefix(literal)?;
+    }
+    rest.is_empty().then_some(array)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn scanning() {
+        let line = "move 13 from 2 to 9";
+        assert_eq!(vec![13, 2, 9], integers::<u8>(line).collect::<Vec<_>>());
+        assert_eq!(Some([13, 2, 9]), array::<u32, 3>(line));
+        assert_eq!(None, array::<u32, 4>(line));
+        assert_eq!(Some([11, 22]), array::<u64, 2>(b"11-22".as_slice()));
+
+        let sensor = "Sensor at x=-2, y=15: beacon at x=3-5";
+        assert_eq!(
+            vec![-2, 15, 3, 5],
+            integers::<i32>(sensor).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![2, 15, 3, 5],
+            integers::<u32>(sensor).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    pub fn limits() {
+        assert_eq!(
+            vec![255, 7],
+            integers::<u8>("255 256 7").collect::<Vec<_>>()
+        );
+        assert_eq!(vec![-128], integers::<i8>("-128 -129").collect::<Vec<_>>());
+        assert_eq!(None, integers::<u32>("no numbers").next());
+    }
+
+    #[test]
+    pub fn prefixes() {
+        assert_eq!(Some((12, ",3]")), prefix::<u32>("12,3]"));
+        assert_eq!(Some((-4, "")), prefix::<i32>("-4"));
+        assert_eq!(None, prefix::<u32>("-4"));
+        assert_eq!(None, prefix::<u32>("[1]"));
+        assert_eq!(None, prefix::<u8>("300"));
+    }
+
+    #[test]
+    pub fn exact_fields() {
+        let range = ["", "-", ""];
+        assert_eq!(Some([11, 22]), fields::<u64, 2>("11-22", &range));
+        assert_eq!(None, fields::<u64, 2>("1x-22", &range));
+        assert_eq!(None, fields::<u64, 2>("11-22-99", &range));
+        assert_eq!(None, fields::<u64, 2>("11-", &range));
+        assert_eq!(None, fields::<u8, 2>("1-256", &range));
+
+        let instruction = ["move ", " from ", " to ", ""];
+        assert_eq!(
+            Some([13, 2, 9]),
+            fields::<usize, 3>("move 13 from 2 to 9", &instruction)
+        );
+        assert_eq!(
+            None,
+            fields::<usize, 3>("move 13 from 2 to 9 ", &instruction)
+        );
+        assert_eq!(Some([-3]), fields::<i32, 1>("x=-3", &["x=", ""]));
+    }
+}
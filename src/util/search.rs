@@ -0,0 +1,357 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque, hash_map::Entry},
+    hash::Hash,
+    ops::Add,
+};
+
+use priority_queue::PriorityQueue;
+
+/// Edge weights and path lengths. Weights must not be negative.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<C: Copy + Ord + Add<Output = C> + Default> Cost for C {}
+
+/// Distances from the nearest start to every reached node, and the node each was reached from.
+#[derive(Debug, Clone)]
+pub struct SearchResult<N, C> {
+    pub distance: HashMap<N, C>,
+    pub previous: HashMap<N, N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Cost> SearchResult<N, C> {
+    fn new() -> Self {
+        Self {
+            distance: HashMap::new(),
+            previous: HashMap::new(),
+        }
+    }
+
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.distance.get(node).copied()
+    }
+
+    /// The route from a start to `node`, both included, or `None` if `node` was not reached.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        self.distance.contains_key(node).then(|| {
+            let mut path = vec![node.clone()];
+            while let Some(p) = self.previous.get(path.last().unwrap()) {
+                path.push(p.clone());
+            }
+            path.reverse();
+            path
+        })
+    }
+}
+
+/// Breadth-first search with unit edges from every node in `starts`.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+) -> SearchResult<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut result = SearchResult::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if let Entry::Vacant(e) = result.distance.entry(start.clone()) {
+            e.insert(0);
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((u, dist)) = queue.pop_front() {
+        for n in neighbors(&u) {
+            if let Entry::Vacant(e) = result.distance.entry(n.clone()) {
+                e.insert(dist + 1);
+                result.previous.insert(n.clone(), u.clone());
+                queue.push_back((n, dist + 1));
+            }
+        }
+    }
+    result
+}
+
+/// Dijkstra's algorithm from every node in `starts`. `neighbors` gives each node's successors
+/// with the weight of the edge to them.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+) -> SearchResult<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut result = SearchResult::new();
+    let mut queue = PriorityQueue::new();
+    for start in starts {
+        result.distance.insert(start.clone(), C::default());
+        queue.push(start, Reverse(C::default()));
+    }
+
+    while let Some((u, Reverse(dist))) = queue.pop() {
+        for (n, weight) in neighbors(&u) {
+            let next = dist + weight;
+            if result.distance.get(&n).is_none_or(|&d| next < d) {
+                result.distance.insert(n.clone(), next);
+                result.previous.insert(n.clone(), u.clone());
+                queue.push_increase(n, Reverse(next));
+            }
+        }
+    }
+    result
+}
+
+/// Like [`SearchResult`], for nodes numbered from 0, with distances and predecessors kept in
+/// vectors indexed by node instead of maps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedResult<C> {
+    pub distance: Vec<Option<C>>,
+    pub previous: Vec<Option<usize>>,
+}
+
+impl<C: Cost> IndexedResult<C> {
+    fn new(len: usize) -> Self {
+        Self {
+            distance: vec![None; len],
+            previous: vec![None; len],
+        }
+    }
+
+    pub fn distance(&self, node: usize) -> Option<C> {
+        self.distance[node]
+    }
+
+    /// The route from a start to `node`, both included, or `None` if `node` was not reached.
+    pub fn path_to(&self, node: usize) -> Option<Vec<usize>> {
+        self.distance[node]?;
+        let mut path = vec![node];
+        while let Some(p) = self.previous[*path.last().unwrap()] {
+            path.push(p);
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// [`bfs`] over the nodes `0..len`.
+pub fn bfs_indexed<I>(
+    len: usize,
+    starts: impl IntoIterator<Item = usize>,
+    mut neighbors: impl FnMut(usize) -> I,
+) -> IndexedResult<usize>
+where
+    I: IntoIterator<Item = usize>,
+{
+    let mut result = IndexedResult::new(len);
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if result.distance[start].is_none() {
+            result.distance[start] = Some(0);
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((u, dist)) = queue.pop_front() {
+        for n in neighbors(u) {
+            if result.distance[n].is_none() {
+                result.distance[n] = Some(dist + 1);
+                result.previous[n] = Some(u);
+                queue.push_back((n, dist + 1));
+            }
+        }
+    }
+    result
+}
+
+/// [`dijkstra`] over the nodes `0..len`. Nodes are queued again when a shorter path to them is
+/// found, and the longer entries skipped when they come out.
+pub fn dijkstra_indexed<C, I>(
+    len: usize,
+    starts: impl IntoIterator<Item = usize>,
+    mut neighbors: impl FnMut(usize) -> I,
+) -> IndexedResult<C>
+where
+    C: Cost,
+    I: IntoIterator<Item = (usize, C)>,
+{
+    let mut result = IndexedResult::new(len);
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        result.distance[start] = Some(C::default());
+        queue.push(Reverse((C::default(), start)));
+    }
+
+    while let Some(Reverse((dist, u))) = queue.pop() {
+        if result.distance[u].is_some_and(|d| d < dist) {
+            continue;
+        }
+        for (n, weight) in neighbors(u) {
+            let next = dist + weight;
+            if result.distance[n].is_none_or(|d| next < d) {
+                result.distance[n] = Some(next);
+                result.previous[n] = Some(u);
+                queue.push(Reverse((next, n)));
+            }
+        }
+    }
+    result
+}
+
+/// A* search from every node in `starts` to the nearest node satisfying `is_goal`.
+/// `heuristic` must never overestimate the remaining cost.
+/// Returns the cost and route of a cheapest path, or `None` if no goal is reachable.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(C, Vec<N>)>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut result = SearchResult::new();
+    let mut queue = PriorityQueue::new();
+    for start in starts {
+        let h = heuristic(&start);
+        result.distance.insert(start.clone(), C::default());
+        queue.push(start, Reverse(h));
+    }
+
+    while let Some((u, _)) = queue.pop() {
+        let dist = result.distance[&u];
+        if is_goal(&u) {
+            return Some((dist, result.path_to(&u).unwrap()));
+        }
+        for (n, weight) in neighbors(&u) {
+            let next = dist + weight;
+            if result.distance.get(&n).is_none_or(|&d| next < d) {
+                let estimate = next + heuristic(&n);
+                result.distance.insert(n.clone(), next);
+                result.previous.insert(n.clone(), u.clone());
+                queue.push_increase(n, Reverse(estimate));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::{Grid, geometry::Point};
+
+    const MAZE: &str = "
+..#....
+.##.##.
+....#..
+.#.##.#
+...#...
+";
+
+    fn open_neighbors(grid: &Grid<bool>) -> impl FnMut(&(usize, usize)) -> Vec<(usize, usize)> {
+        |&p| grid.neighbors4(p).filter(|&n| grid[n]).collect()
+    }
+
+    #[test]
+    pub fn breadth_first() {
+        let grid = Grid::parse(MAZE, |c| c == '.').unwrap();
+        let result = bfs([(0, 0)], open_neighbors(&grid));
+        assert_eq!(Some(16), result.distance(&(6, 4)));
+        assert_eq!(None, result.distance(&(2, 0)));
+
+        let path = result.path_to(&(6, 4)).unwrap();
+        assert_eq!(17, path.len());
+        assert_eq!((0, 0), path[0]);
+        assert!(
+            path.windows(2)
+                .all(|w| Point::from(w[0]).manhattan(w[1].into()) == 1)
+        );
+        assert_eq!(None, result.path_to(&(2, 0)));
+    }
+
+    #[test]
+    pub fn multi_source() {
+        let grid = Grid::parse(MAZE, |c| c == '.').unwrap();
+        let result = bfs([(0, 0), (6, 4)], open_neighbors(&grid));
+        assert_eq!(Some(0), result.distance(&(6, 4)));
+        assert_eq!(Some(1), result.distance(&(5, 4)));
+        assert_eq!(Some(2), result.distance(&(0, 2)));
+        assert_eq!(vec![(6, 4), (5, 4)], result.path_to(&(5, 4)).unwrap());
+    }
+
+    #[test]
+    pub fn weighted() {
+        let edges = HashMap::from([
+            ('a', vec![('b', 7), ('c', 9), ('f', 14)]),
+            ('b', vec![('a', 7), ('c', 10), ('d', 15)]),
+            ('c', vec![('a', 9), ('b', 10), ('d', 11), ('f', 2)]),
+            ('d', vec![('b', 15), ('c', 11), ('e', 6)]),
+            ('e', vec![('d', 6), ('f', 9)]),
+            ('f', vec![('a', 14), ('c', 2), ('e', 9)]),
+        ]);
+        let neighbors = |n: &char| edges[n].clone();
+
+        let result = dijkstra(['a'], neighbors);
+        assert_eq!(Some(20), result.distance(&'e'));
+        assert_eq!(Some(vec!['a', 'c', 'f', 'e']), result.path_to(&'e'));
+        assert_eq!(Some(20), result.distance(&'d'));
+
+        assert_eq!(
+            Some((20, vec!['a', 'c', 'f', 'e'])),
+            astar(['a'], neighbors, |_| 0, |&n| n == 'e')
+        );
+        assert_eq!(None, astar(['a'], neighbors, |_| 0, |&n| n == 'z'));
+    }
+
+    #[test]
+    pub fn indexed() {
+        let grid = Grid::parse(MAZE, |c| c == '.').unwrap();
+        let by_point = bfs([(0, 0)], open_neighbors(&grid));
+        let neighbors = |v: usize| {
+            let p = grid.position_of(v);
+            grid.neighbors4(p)
+                .filter(|&n| grid[n])
+                .map(|n| grid.index_of(n))
+                .collect::<Vec<_>>()
+        };
+        let result = bfs_indexed(grid.len(), [0], neighbors);
+        for v in 0..grid.len() {
+            assert_eq!(by_point.distance(&grid.position_of(v)), result.distance(v));
+        }
+        let goal = grid.index_of((6, 4));
+        let path = result.path_to(goal).unwrap();
+        assert_eq!(
+            (17, Some(&0), Some(&goal)),
+            (path.len(), path.first(), path.last())
+        );
+        assert_eq!(None, result.path_to(grid.index_of((2, 0))));
+
+        let weighted = dijkstra_indexed(grid.len(), [0], |v| {
+            neighbors(v).into_iter().map(|n| (n, 2))
+        });
+        assert_eq!(Some(32), weighted.distance(goal));
+        assert_eq!(Some(17), weighted.path_to(goal).map(|p| p.len()));
+    }
+
+    #[test]
+    pub fn a_star_matches_bfs() {
+        let grid = Grid::parse(MAZE, |c| c == '.').unwrap();
+        let goal = (6, 4);
+        let (cost, path) = astar(
+            [(0, 0)],
+            |&p| grid.neighbors4(p).filter(|&n| grid[n]).map(|n| (n, 1)),
+            |&p| Point::from(p).manhattan(goal.into()),
+            |&p| p == goal,
+        )
+        .unwrap();
+        assert_eq!(16, cost);
+        assert_eq!(17, path.len());
+        assert_eq!(Some(goal), path.last().copied());
+    }
+}
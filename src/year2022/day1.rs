@@ -1,20 +1,20 @@
+use crate::{
+    provider,
+    solution::{Answer, Solution},
+    util::{ParseError, parse},
+};
+
 pub struct Elf {
     calories: Vec<usize>,
 }
 
 // #[aoc_generator(day1)]
-pub fn parse_elves(input: &str) -> Vec<Elf> {
-    input
-        .lines()
-        .collect::<Vec<_>>()
-        .split(|l| l.is_empty())
-        .map(|cals| Elf {
-            calories: cals
-                .iter()
-                .map(|c| c.parse().expect("input contains integers"))
-                .collect(),
+pub fn parse_elves(input: &str) -> Result<Vec<Elf>, ParseError> {
+    parse::paragraphs(input, |cals| {
+        Ok(Elf {
+            calories: parse::numbers(cals, "a calorie count")?,
         })
-        .collect()
+    })
 }
 
 impl Elf {
@@ -38,12 +38,39 @@ pub fn solve_part2(input: &[Elf]) -> usize {
     calories.iter().take(3).sum()
 }
 
-pub fn main() {
-    let input = std::fs::read_to_string("input/2022/day1.txt").unwrap();
-    let elves = parse_elves(&input);
+pub fn part1(input: &str) -> usize {
+    solve_part1(&parse_elves(input).unwrap())
+}
+
+pub fn part2(input: &str) -> usize {
+    solve_part2(&parse_elves(input).unwrap())
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed<'a> = Vec<Elf>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse_elves(input)
+    }
+
+    fn part1(elves: &Self::Parsed<'_>) -> Answer {
+        solve_part1(elves).into()
+    }
+
+    fn part2(elves: &Self::Parsed<'_>) -> Answer {
+        solve_part2(elves).into()
+    }
+}
+
+pub fn main() -> Result<(), provider::Error> {
+    let input = provider::input(2022, 1)?;
+    let elves = parse_elves(&input).unwrap();
     let solution = solve_part2(&elves);
 
     println!("{}", solution);
+    Ok(())
 }
 
 // #[cfg(test)]
@@ -53,7 +80,7 @@ pub fn main() {
 //     #[test]
 //     pub fn solve() {
 //         let input = std::fs::read_to_string("input/2022/day1.txt").unwrap();
-//         let elves = parse_elves(&input);
+//         let elves = parse_elves(&input).unwrap();
 //         let soluton = solve_part1(&elves);
 //     }
 // }
@@ -1,5 +1,11 @@
 use std::collections::VecDeque;
 
+use crate::{
+    provider,
+    solution::{Answer, Solution},
+    util::{ParseError, parse},
+};
+
 #[derive(Debug)]
 struct Cpu {
     cycles_completed: usize,
@@ -48,26 +54,27 @@ impl Cpu {
 }
 
 #[derive(Debug, Clone, Copy)]
-enum Instruction {
+pub enum Instruction {
     AddX(isize),
     NoOp,
 }
 
-fn parse_instructions(input: &str) -> VecDeque<Instruction> {
-    input
-        .lines()
-        .map(|l| l.split_ascii_whitespace())
-        .map(|mut splits| match splits.next().unwrap() {
-            "noop" => Instruction::NoOp,
-            "addx" => Instruction::AddX(splits.next().unwrap().parse().unwrap()),
-            other => panic!("Unknown instruction {other:?}"),
-        })
-        .collect()
+fn parse_instruction(line: &str) -> Result<Instruction, ParseError> {
+    match line.split_once(' ') {
+        None if line == "noop" => Ok(Instruction::NoOp),
+        Some(("addx", n)) => Ok(Instruction::AddX(
+            parse::number(n, "an integer").map_err(|e| e.within(line, n))?,
+        )),
+        _ => Err(ParseError::new(line, "`noop` or `addx N`")),
+    }
+}
+
+pub fn parse_instructions(input: &str) -> Result<VecDeque<Instruction>, ParseError> {
+    parse::lines(input, parse_instruction).map(VecDeque::from)
 }
 
-fn sample_interesting_cycles(input: &str) -> Vec<isize> {
+fn sample_interesting_cycles(instructions: VecDeque<Instruction>) -> Vec<isize> {
     let mut samples = Vec::with_capacity(6);
-    let instructions = parse_instructions(input);
     let mut cpu = Cpu::new(instructions);
     while !cpu.instruction_buffer.is_empty() {
         cpu.cycle();
@@ -81,16 +88,15 @@ fn sample_interesting_cycles(input: &str) -> Vec<isize> {
     samples
 }
 
-pub fn part1(input: &str) -> isize {
-    let samples = sample_interesting_cycles(input);
+fn signal_strength(instructions: VecDeque<Instruction>) -> isize {
+    let samples = sample_interesting_cycles(instructions);
     samples.iter().sum()
 }
 
-#[allow(dead_code, unused_variables)]
-pub fn part2(input: &str) -> String {
+#[allow(unused_variables)]
+fn render_crt(instructions: VecDeque<Instruction>) -> String {
     const WIDTH: usize = 40;
     const HEIGHT: usize = 6;
-    let instructions = parse_instructions(input);
     let mut cpu = Cpu::new(instructions);
     let mut output = String::with_capacity((WIDTH * HEIGHT) + HEIGHT); // newlines
     for _ in 0..WIDTH * HEIGHT {
@@ -113,23 +119,50 @@ pub fn part2(input: &str) -> String {
     output
 }
 
-pub fn main() {
-    let input = std::fs::read_to_string("input/2022/day10.txt").unwrap();
+pub fn part1(input: &str) -> isize {
+    signal_strength(parse_instructions(input).unwrap())
+}
+
+pub fn part2(input: &str) -> String {
+    render_crt(parse_instructions(input).unwrap())
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed<'a> = VecDeque<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse_instructions(input)
+    }
+
+    fn part1(instructions: &Self::Parsed<'_>) -> Answer {
+        signal_strength(instructions.clone()).into()
+    }
+
+    fn part2(instructions: &Self::Parsed<'_>) -> Answer {
+        Answer::Rendering(render_crt(instructions.clone()))
+    }
+}
+
+pub fn main() -> Result<(), provider::Error> {
+    let input = provider::input(2022, 10)?;
     dbg!(part1(&input));
     println!("{}", part2(&input));
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples;
+    use once_cell::sync::Lazy;
 
-    const EXAMPLE: &str = r"noop
-addx 3
-addx -5";
+    static EXAMPLE: Lazy<String> = Lazy::new(|| examples::input(2022, 10, 2));
 
     #[test]
     pub fn test_cpu() {
-        let instructions = parse_instructions(EXAMPLE);
+        let instructions = parse_instructions(&EXAMPLE).unwrap();
         let mut cpu = Cpu::new(instructions);
 
         dbg!(&cpu);
@@ -160,9 +193,9 @@ addx -5";
 
     #[test]
     pub fn part1_example() {
-        let input = std::fs::read_to_string("input/2022/day10_example1.txt").unwrap();
+        let input = examples::input(2022, 10, 1);
 
-        let samples = sample_interesting_cycles(&input);
+        let samples = sample_interesting_cycles(parse_instructions(&input).unwrap());
         assert_eq!(vec![420, 1140, 1800, 2940, 2880, 3960], samples);
 
         assert_eq!(13140, part1(&input));
@@ -177,7 +210,7 @@ addx -5";
 
     #[test]
     pub fn part2_example() {
-        let input = std::fs::read_to_string("input/2022/day10_example1.txt").unwrap();
+        let input = examples::input(2022, 10, 1);
 
         for (i, (e, a)) in PART2_EXPECTED
             .lines()
@@ -1,14 +1,21 @@
 use std::{collections::VecDeque, str::FromStr};
 
 use itertools::Itertools;
-use once_cell::sync::Lazy;
-use regex::Regex;
+
+use crate::{
+    provider,
+    solution::{Answer, Solution},
+    util::{
+        ParseError, parse,
+        scan::{self, Integer},
+    },
+};
 
 type Item = u64;
 type Operand = Option<Item>; // None == old
 
-#[derive(Debug)]
-struct Monkey {
+#[derive(Debug, Clone)]
+pub struct Monkey {
     items: VecDeque<Item>,
     operator: Operator,
     operand_left: Operand,
@@ -31,62 +38,119 @@ impl Monkey {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum Operator {
     Add,
     Multiply,
 }
 
 impl FromStr for Operator {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "*" => Ok(Operator::Multiply),
             "+" => Ok(Operator::Add),
-            _ => Err(()),
+            _ => Err(ParseError::new(s, "`*` or `+`")),
         }
     }
 }
 
-static MONKEY_REGEX: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(
-        r"(?x-u)\s*
-Monkey \s (?P<index>\d+):\s*
-Starting \s items: \s (?P<items>[\d,\s]+)\s*
-Operation: \s new \s = \s (?P<oper_left>old|\d+)\s(?P<operator>[\*\+])\s(?P<oper_right>old|\d+)\s*
-Test: \s divisible \s by \s (?P<test_divisor>\d+)\s*
-If \s true: \s throw \s to \s monkey \s (?P<true_monkey>\d+)\s*
-If \s false: \s throw \s to \s monkey \s (?P<false_monkey>\d+)\s*
-",
-    )
-    .unwrap()
-});
-static ITEM_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?-u)(\d+)[, ]*").unwrap());
-
-fn parse_monkeys(input: &str) -> Vec<Monkey> {
-    MONKEY_REGEX
-        .captures_iter(input)
-        .map(|c| {
-            let items = ITEM_REGEX
-                .captures_iter(&c["items"])
-                .filter_map(|i| i[1].parse().ok())
-                .collect();
-
-            Monkey {
-                items,
-                operator: c["operator"].parse().unwrap(),
-                operand_left: c["oper_left"].parse().ok(),
-                operand_right: c["oper_right"].parse().ok(),
-                test_divisor: c["test_divisor"].parse().unwrap(),
-                true_monkey: c["true_monkey"].parse().unwrap(),
-                false_monkey: c["false_monkey"].parse().unwrap(),
-                num_inspects: Default::default(),
-            }
-        })
-        .collect()
+/// The rest of the paragraph's next line, which must start with `label`.
+fn field<'a>(
+    paragraph: &'a str,
+    lines: &mut impl Iterator<Item = &'a str>,
+    label: &str,
+) -> Result<&'a str, ParseError> {
+    let line = lines.next().unwrap_or(&paragraph[paragraph.len()..]);
+    line.trim_start()
+        .strip_prefix(label)
+        .ok_or_else(|| ParseError::at(paragraph, line, format!("a line starting `{label}`")))
+}
+
+/// `s` as a number and nothing else, apart from surrounding spaces.
+fn number<T: Integer>(paragraph: &str, s: &str) -> Result<T, ParseError> {
+    match scan::prefix(s.trim()) {
+        Some((n, "")) => Ok(n),
+        _ => Err(ParseError::at(paragraph, s.trim(), "a number")),
+    }
 }
 
+fn parse_items(paragraph: &str, s: &str) -> Result<VecDeque<Item>, ParseError> {
+    match s.trim() {
+        "" => Ok(VecDeque::new()),
+        items => items.split(", ").map(|n| number(paragraph, n)).collect(),
+    }
+}
+
+fn parse_operand(paragraph: &str, s: &str) -> Result<Operand, ParseError> {
+    match (s, scan::prefix(s)) {
+        ("old", _) => Ok(None),
+        (_, Some((n, ""))) => Ok(Some(n)),
+        _ => Err(ParseError::at(paragraph, s, "`old` or a number")),
+    }
+}
+
+/// A monkey, which can only throw to one of the `monkeys` there are.
+fn parse_monkey(paragraph: &str, monkeys: usize) -> Result<Monkey, ParseError> {
+    let mut lines = paragraph.lines();
+    field(paragraph, &mut lines, "Monkey ")?;
+    let items = field(paragraph, &mut lines, "Starting items:")?;
+    let operation = field(paragraph, &mut lines, "Operation: new =")?;
+    let test_divisor = field(paragraph, &mut lines, "Test: divisible by")?;
+    let true_monkey = field(paragraph, &mut lines, "If true: throw to monkey")?;
+    let false_monkey = field(paragraph, &mut lines, "If false: throw to monkey")?;
+    if let Some(extra) = lines.next() {
+        return Err(ParseError::at(paragraph, extra, "a blank line"));
+    }
+
+    let mut words = operation.split_ascii_whitespace();
+    let (Some(left), Some(operator), Some(right), None) =
+        (words.next(), words.next(), words.next(), words.next())
+    else {
+        return Err(ParseError::at(
+            paragraph,
+            operation.trim(),
+            "an operation like `old * 19`",
+        ));
+    };
+
+    let divisor = number(paragraph, test_divisor)?;
+    if divisor == 0 {
+        return Err(ParseError::at(paragraph, test_divisor.trim(), "a divisor above 0"));
+    }
+    let target = |s: &str| {
+        let monkey = number(paragraph, s)?;
+        if monkey >= monkeys {
+            return Err(ParseError::at(
+                paragraph,
+                s.trim(),
+                format!("a monkey from 0 to {}", monkeys - 1),
+            ));
+        }
+        Ok(monkey)
+    };
+
+    Ok(Monkey {
+        items: parse_items(paragraph, items)?,
+        operator: operator
+            .parse()
+            .map_err(|e: ParseError| e.within(paragraph, operator))?,
+        operand_left: parse_operand(paragraph, left)?,
+        operand_right: parse_operand(paragraph, right)?,
+        test_divisor: divisor,
+        true_monkey: target(true_monkey)?,
+        false_monkey: target(false_monkey)?,
+        num_inspects: Default::default(),
+    })
+}
+
+pub fn parse_monkeys(input: &str) -> Result<Vec<Monkey>, ParseError> {
+    let monkeys = parse::split_paragraphs(input).count();
+    parse::paragraphs(input, |paragraph| parse_monkey(paragraph, monkeys))
+}
+
+#[derive(Clone, Copy)]
 enum Part {
     One,
     Two,
@@ -121,68 +185,108 @@ fn monkey_business(monkeys: &[Monkey]) -> usize {
         .fold(1, |a, x| a * x.0)
 }
 
-pub fn part1(input: &str) -> usize {
-    let mut monkeys = parse_monkeys(input);
-    for _ in 0..20 {
-        monkey_around(&mut monkeys, Part::One);
+fn simulate(monkeys: &[Monkey], rounds: usize, part: Part) -> usize {
+    let mut monkeys = monkeys.to_vec();
+    for _ in 0..rounds {
+        monkey_around(&mut monkeys, part);
     }
     monkey_business(&monkeys)
 }
 
-#[allow(dead_code, unused_variables)]
+pub fn part1(input: &str) -> usize {
+    simulate(&parse_monkeys(input).unwrap(), 20, Part::One)
+}
+
 pub fn part2(input: &str) -> usize {
-    let mut monkeys = parse_monkeys(input);
-    for _ in 0..10000 {
-        monkey_around(&mut monkeys, Part::Two);
+    simulate(&parse_monkeys(input).unwrap(), 10000, Part::Two)
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed<'a> = Vec<Monkey>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse_monkeys(input)
+    }
+
+    fn part1(monkeys: &Self::Parsed<'_>) -> Answer {
+        simulate(monkeys, 20, Part::One).into()
+    }
+
+    fn part2(monkeys: &Self::Parsed<'_>) -> Answer {
+        simulate(monkeys, 10000, Part::Two).into()
     }
-    monkey_business(&monkeys)
 }
 
-pub fn main() {
-    let input = std::fs::read_to_string("input/2022/day11.txt").unwrap();
+pub fn main() -> Result<(), provider::Error> {
+    let input = provider::input(2022, 11)?;
     dbg!(part1(&input));
     dbg!(part2(&input));
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples;
+    use once_cell::sync::Lazy;
 
-    const EXAMPLE: &str = r"Monkey 0:
-  Starting items: 79, 98
-  Operation: new = old * 19
-  Test: divisible by 23
-    If true: throw to monkey 2
-    If false: throw to monkey 3
-
-Monkey 1:
-  Starting items: 54, 65, 75, 74
-  Operation: new = old + 6
-  Test: divisible by 19
-    If true: throw to monkey 2
-    If false: throw to monkey 0
-
-Monkey 2:
-  Starting items: 79, 60, 97
-  Operation: new = old * old
-  Test: divisible by 13
-    If true: throw to monkey 1
-    If false: throw to monkey 3
-
-Monkey 3:
-  Starting items: 74
-  Operation: new = old + 3
-  Test: divisible by 17
-    If true: throw to monkey 0
-    If false: throw to monkey 1";
+    static EXAMPLE: Lazy<String> = Lazy::new(|| examples::input(2022, 11, 1));
 
     #[test]
     pub fn part1_example() {
-        assert_eq!(10605, part1(EXAMPLE));
+        assert_eq!(10605, part1(&EXAMPLE));
     }
 
     #[test]
     pub fn part2_example() {
-        assert_eq!(2713310158, part2(EXAMPLE));
+        assert_eq!(2713310158, part2(&EXAMPLE));
+    }
+
+    #[test]
+    pub fn parse_errors() {
+        let broken = EXAMPLE.replacen("old * 19", "old ^ 19", 1);
+        let error = parse_monkeys(&broken).unwrap_err();
+        assert_eq!((3, 24, "^"), (error.line, error.column, error.text.as_str()));
+
+        let broken = EXAMPLE.replacen("Test: divisible", "Test: dividable", 1);
+        let error = parse_monkeys(&broken).unwrap_err();
+        assert_eq!((4, 1), (error.line, error.column));
+
+        let broken = EXAMPLE.replacen("79, 98", "79, 98, oops, 99999999999999999999", 1);
+        let error = parse_monkeys(&broken).unwrap_err();
+        assert_eq!(
+            (2, 27, "oops"),
+            (error.line, error.column, error.text.as_str())
+        );
+        let broken = EXAMPLE.replacen("79, 98", "79, 98, 99999999999999999999", 1);
+        let error = parse_monkeys(&broken).unwrap_err();
+        assert_eq!((2, 27), (error.line, error.column));
+
+        let broken = EXAMPLE.replacen("divisible by 23", "divisible by 2x", 1);
+        let error = parse_monkeys(&broken).unwrap_err();
+        assert_eq!((4, 22, "2x"), (error.line, error.column, error.text.as_str()));
+
+        let broken = EXAMPLE.replacen("79, 98", "79,98", 1);
+        assert!(parse_monkeys(&broken).is_err());
+
+        let broken = EXAMPLE.replacen("throw to monkey 2", "throw to monkey 9", 1);
+        let error = parse_monkeys(&broken).unwrap_err();
+        assert_eq!(
+            (5, 30, "9", "a monkey from 0 to 3"),
+            (
+                error.line,
+                error.column,
+                error.text.as_str(),
+                error.expected.as_str()
+            )
+        );
+        let broken = EXAMPLE.replacen("divisible by 23", "divisible by 0", 1);
+        let error = parse_monkeys(&broken).unwrap_err();
+        assert_eq!(
+            (4, 22, "a divisor above 0"),
+            (error.line, error.column, error.expected.as_str())
+        );
     }
 }
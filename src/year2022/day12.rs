@@ -1,173 +1,408 @@
-use priority_queue::PriorityQueue;
-use std::cmp::Reverse;
+use std::{cell::OnceCell, cmp::Ordering};
 use tinyvec::ArrayVec;
 
+use crate::{
+    provider,
+    solution::{Answer, Part, Solution},
+    util::{
+        Grid, ParseError, Point,
+        geometry::{NEIGHBORS_4, NEIGHBORS_8},
+        parse,
+        search::{self, IndexedResult},
+    },
+};
+
+/// How a heightmap may be crossed. Heights are the tiles' bytes, with `S` as `a` and `E` as `z`.
+#[derive(Debug, Clone)]
+pub struct Rules {
+    /// Highest step up
+    pub max_climb: u8,
+    /// Highest step down, if limited
+    pub max_descent: Option<u8>,
+    /// Whether diagonal neighbors can be stepped to as well as orthogonal ones
+    pub diagonal: bool,
+    /// Cost of a step given the height difference, positive when climbing
+    pub step_cost: fn(i16) -> u32,
+    /// Tiles that can be stepped onto and off from any height, at the cost of a level step
+    pub passable: Vec<u8>,
+    /// Tiles that can never be stepped onto
+    pub impassable: Vec<u8>,
+}
+
+impl Default for Rules {
+    /// The puzzle's rules: climb at most one level, descend any distance, four directions,
+    /// every step costs 1.
+    fn default() -> Self {
+        Self {
+            max_climb: 1,
+            max_descent: None,
+            diagonal: false,
+            step_cost: |_| 1,
+            passable: Vec::new(),
+            impassable: Vec::new(),
+        }
+    }
+}
+
+impl Rules {
+    /// Cost of stepping from `src` to `dest`, or `None` if that step is not allowed.
+    pub fn step(&self, src: u8, dest: u8) -> Option<u32> {
+        if self.impassable.contains(&src) || self.impassable.contains(&dest) {
+            return None;
+        }
+        if self.passable.contains(&src) || self.passable.contains(&dest) {
+            return Some((self.step_cost)(0));
+        }
+        let rise = dest as i16 - src as i16;
+        let allowed = rise <= self.max_climb as i16
+            && self.max_descent.is_none_or(|d| -rise <= d as i16);
+        allowed.then(|| (self.step_cost)(rise))
+    }
+}
+
 #[derive(Debug)]
-struct Graph {
-    vertices: Vec<u8>,
-    incoming: Vec<ArrayVec<[usize; 4]>>,
+pub struct Graph {
+    vertices: Grid<u8>,
+    /// For each vertex, the vertices that can step to it and the cost of that step
+    incoming: Vec<ArrayVec<[(usize, u32); 8]>>,
     start: usize,
     peak: usize,
+    /// Whether every step costs 1
+    unit_cost: bool,
+    /// Shortest paths, searched for the first time they are asked for
+    paths: OnceCell<Paths>,
 }
 
-impl Graph {
-    #[allow(clippy::needless_range_loop)]
-    fn dijkstra(&self) -> (Vec<u32>, Vec<Option<usize>>) {
-        let mut distance: Vec<u32> = vec![u32::MAX; self.vertices.len()];
-        let mut previous: Vec<Option<usize>> = vec![None; self.vertices.len()];
-        distance[self.peak] = 0;
-        let mut unvisited = PriorityQueue::with_capacity(self.vertices.len());
-        for i in 0..self.vertices.len() {
-            unvisited.push(i, Reverse(distance[i]));
-        }
+/// Distances to the peak from every vertex that can reach it, and the routes there.
+pub type Paths = IndexedResult<usize>;
 
-        while let Some((u, dist)) = unvisited.pop() {
-            for &n in self.incoming[u].iter() {
-                if unvisited.get(&n).is_some() && dist.0.saturating_add(1) < distance[n] {
-                    distance[n] = dist.0 + 1;
-                    unvisited.change_priority(&n, Reverse(dist.0 + 1));
-                    previous[n] = Some(u);
-                }
+impl Graph {
+    /// Shortest paths to the peak, using [`Graph::bfs`] when every step costs 1 and
+    /// [`Graph::dijkstra`] otherwise. Searched once, on the first call.
+    pub fn shortest_paths(&self) -> &Paths {
+        self.paths.get_or_init(|| {
+            if self.unit_cost {
+                self.bfs()
+            } else {
+                self.dijkstra()
             }
-        }
+        })
+    }
 
-        (distance, previous)
-    }
-}
-
-#[allow(dead_code)]
-fn dijkstra_path(previous: &[Option<usize>], from: usize) -> Vec<usize> {
-    let mut path = Vec::new();
-    path.push(from);
-    let mut v = from;
-    while let Some(p) = previous[v] {
-        path.push(p);
-        v = p;
-    }
-    path.reverse();
-    path
-}
-
-#[allow(clippy::needless_range_loop)]
-fn parse_grid(input: &str) -> Graph {
-    let grid: Vec<Vec<u8>> = input.lines().map(|l| l.as_bytes().into()).collect();
-    let height = grid.len();
-    let width = grid[0].len();
-    let idx = |i, j| (i * width) + j;
-
-    let mut start = 0;
-    let mut peak = 0;
-    let mut vertices = vec![0; width * height];
-    let mut incoming: Vec<ArrayVec<[usize; 4]>> = vec![Default::default(); width * height];
-    for i in 0..height {
-        for j in 0..width {
-            let v = idx(i, j);
-            vertices[v] = match grid[i][j] {
-                b'S' => {
-                    start = v;
-                    b'a'
-                }
-                b'E' => {
-                    peak = v;
-                    b'z'
-                }
-                b => b,
-            };
-        }
+    /// Shortest paths counted in steps, found by a breadth-first search back from the peak.
+    /// Step costs are ignored.
+    pub fn bfs(&self) -> Paths {
+        search::bfs_indexed(self.vertex_count(), [self.peak], |u| {
+            self.incoming[u].iter().map(|&(n, _)| n)
+        })
     }
-    for i in 0..height {
-        for j in 0..width {
-            let v = idx(i, j);
-            let mut maybe_push = |ii, jj| {
-                let u = idx(ii, jj);
-                if accessible(vertices[v], vertices[u]) {
-                    incoming[u].push(v);
-                }
-            };
-            if i > 0 {
-                maybe_push(i - 1, j);
-            }
-            if j > 0 {
-                maybe_push(i, j - 1);
+
+    /// Shortest paths by step cost, searched back from the peak.
+    pub fn dijkstra(&self) -> Paths {
+        search::dijkstra_indexed(self.vertex_count(), [self.peak], |u| {
+            self.incoming[u].iter().map(|&(n, cost)| (n, cost as usize))
+        })
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    pub fn peak(&self) -> usize {
+        self.peak
+    }
+
+    /// The vertices that can step to `v` and the cost of each step.
+    pub fn incoming(&self, v: usize) -> &[(usize, u32)] {
+        &self.incoming[v]
+    }
+}
+
+/// Vertices from `from` to the peak, or `None` if the peak cannot be reached from `from`.
+fn route_to_peak(paths: &Paths, from: usize) -> Option<Vec<usize>> {
+    let mut route = paths.path_to(from)?;
+    route.reverse();
+    Some(route)
+}
+
+/// The heightmap with each step of the route from `from` drawn as an arrow towards the next, or
+/// `None` if the peak cannot be reached from `from`.
+pub fn render_route(graph: &Graph, paths: &Paths, from: usize) -> Option<String> {
+    let mut map = graph.vertices.map(|&h| h as char);
+    for step in route_to_peak(paths, from)?.windows(2) {
+        let (x, y) = map.position_of(step[0]);
+        let (nx, ny) = map.position_of(step[1]);
+        map[(x, y)] = match (nx.cmp(&x), ny.cmp(&y)) {
+            (Ordering::Greater, Ordering::Equal) => '>',
+            (Ordering::Less, Ordering::Equal) => '<',
+            (Ordering::Equal, Ordering::Greater) => 'v',
+            (Ordering::Equal, _) => '^',
+            (Ordering::Greater, Ordering::Greater) => '↘',
+            (Ordering::Greater, _) => '↗',
+            (Ordering::Less, Ordering::Greater) => '↙',
+            (Ordering::Less, _) => '↖',
+        };
+    }
+    let peak = map.position_of(graph.peak);
+    map[peak] = 'E';
+    Some(map.to_string())
+}
+
+pub fn parse_grid(input: &str) -> Result<Graph, ParseError> {
+    parse_grid_with(input, &Rules::default())
+}
+
+/// Builds the graph of steps allowed by `rules` between neighboring tiles.
+pub fn parse_grid_with(input: &str, rules: &Rules) -> Result<Graph, ParseError> {
+    let mut vertices = parse::grid(input, "an ASCII tile", |c| {
+        u8::try_from(c).ok().filter(u8::is_ascii_graphic)
+    })?;
+    let mut start = None;
+    let mut peak = None;
+    for (v, height) in vertices.iter_mut().enumerate() {
+        match *height {
+            b'S' => {
+                start = Some(v);
+                *height = b'a';
             }
-            if i < height - 1 {
-                maybe_push(i + 1, j);
+            b'E' => {
+                peak = Some(v);
+                *height = b'z';
             }
-            if j < width - 1 {
-                maybe_push(i, j + 1);
+            _ => {}
+        }
+    }
+    let missing = |tile| ParseError::at(input, &input[input.len()..], tile);
+    let start = start.ok_or_else(|| missing("a start tile `S`"))?;
+    let peak = peak.ok_or_else(|| missing("a peak tile `E`"))?;
+
+    let mut incoming: Vec<ArrayVec<[(usize, u32); 8]>> =
+        vec![Default::default(); vertices.len()];
+    let mut unit_cost = true;
+    for pos in vertices.positions() {
+        let v = vertices.index_of(pos);
+        let offsets: &[Point] = if rules.diagonal {
+            &NEIGHBORS_8
+        } else {
+            &NEIGHBORS_4
+        };
+        for n in offsets.iter().filter_map(|&o| vertices.offset(pos, o)) {
+            if let Some(cost) = rules.step(vertices[pos], vertices[n]) {
+                unit_cost &= cost == 1;
+                incoming[vertices.index_of(n)].push((v, cost));
             }
         }
     }
 
-    Graph {
+    Ok(Graph {
         vertices,
         incoming,
         start,
         peak,
-    }
-}
-
-fn accessible(src: u8, dest: u8) -> bool {
-    dest <= src + 1
+        unit_cost,
+        paths: OnceCell::new(),
+    })
 }
 
-fn part1(graph: &Graph, distance: &[u32], _previous: &[Option<usize>]) -> u32 {
-    // dbg!(&graph);
-    // let path = dijkstra_path(previous, graph.start);
-    // dbg!(&path);
-    // path.len() - 1
-    distance[graph.start]
+/// Steps from the start to the peak, or `None` if the peak cannot be reached.
+fn steps_from_start(graph: &Graph, paths: &Paths) -> Option<usize> {
+    paths.distance(graph.start)
 }
 
-#[allow(dead_code, unused_variables)]
-fn part2(graph: &Graph, distance: &[u32], previous: &[Option<usize>]) -> u32 {
-    let closest = graph
+/// The lowest vertex closest to the peak, or `None` if the peak cannot be reached from any.
+fn closest_lowest(graph: &Graph, paths: &Paths) -> Option<usize> {
+    graph
         .vertices
         .iter()
         .enumerate()
-        .filter_map(|(i, &h)| if h == b'a' { Some(i) } else { None })
-        .min_by_key(|&i| distance[i])
-        .unwrap();
-    // let path = dijkstra_path(&previous, closest);
-    // dbg!(&path);
-    // path.len() - 1
-    distance[closest]
+        .filter(|&(_, &h)| h == b'a')
+        .filter_map(|(i, _)| Some((paths.distance(i)?, i)))
+        .min()
+        .map(|(_, i)| i)
+}
+
+fn steps_from_lowest(graph: &Graph, paths: &Paths) -> Option<usize> {
+    paths.distance(closest_lowest(graph, paths)?)
 }
 
-pub fn main() {
+pub fn part1(input: &str) -> Option<usize> {
+    let graph = parse_grid(input).unwrap();
+    steps_from_start(&graph, graph.shortest_paths())
+}
+
+pub fn part2(input: &str) -> Option<usize> {
+    let graph = parse_grid(input).unwrap();
+    steps_from_lowest(&graph, graph.shortest_paths())
+}
+
+/// The steps as the answer, or "unreachable" if there is no way to the peak.
+fn steps_answer(steps: Option<usize>) -> Answer {
+    steps.map_or_else(|| "unreachable".into(), Answer::from)
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed<'a> = Graph;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse_grid(input)
+    }
+
+    fn part1(graph: &Self::Parsed<'_>) -> Answer {
+        steps_answer(steps_from_start(graph, graph.shortest_paths()))
+    }
+
+    fn part2(graph: &Self::Parsed<'_>) -> Answer {
+        steps_answer(steps_from_lowest(graph, graph.shortest_paths()))
+    }
+
+    fn render(graph: &Self::Parsed<'_>, part: Part) -> Option<String> {
+        let paths = graph.shortest_paths();
+        let from = match part {
+            Part::One => graph.start,
+            Part::Two => closest_lowest(graph, paths)?,
+        };
+        render_route(graph, paths, from)
+    }
+}
+
+pub fn main() -> Result<(), provider::Error> {
     let start = std::time::Instant::now();
-    let input = std::fs::read_to_string("input/2022/day12.txt").unwrap();
-    let graph = parse_grid(&input);
-    let (distance, previous) = graph.dijkstra();
-    dbg!(part1(&graph, &distance, &previous));
-    dbg!(part2(&graph, &distance, &previous));
+    let input = provider::input(2022, 12)?;
+    let graph = parse_grid(&input).unwrap();
+    let paths = graph.shortest_paths();
+    dbg!(steps_from_start(&graph, paths));
+    dbg!(steps_from_lowest(&graph, paths));
     println!(
         "Time: {}us",
         std::time::Instant::now().duration_since(start).as_micros()
     );
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples;
+    use once_cell::sync::Lazy;
 
-    const EXAMPLE: &str = r"Sabqponm
-abcryxxl
-accszExk
-acctuvwj
-abdefghi";
+    static EXAMPLE: Lazy<String> = Lazy::new(|| examples::input(2022, 12, 1));
 
     #[test]
     pub fn part1_example() {
-        let graph = parse_grid(EXAMPLE);
-        let (distance, previous) = graph.dijkstra();
-        assert_eq!(31, part1(&graph, &distance, &previous));
+        let graph = parse_grid(&EXAMPLE).unwrap();
+        assert_eq!(Some(31), steps_from_start(&graph, graph.shortest_paths()));
+        assert_eq!(Some(31), part1(&EXAMPLE));
     }
 
     #[test]
     pub fn part2_example() {
-        let graph = parse_grid(EXAMPLE);
-        let (distance, previous) = graph.dijkstra();
-        assert_eq!(29, part2(&graph, &distance, &previous));
+        let graph = parse_grid(&EXAMPLE).unwrap();
+        assert_eq!(Some(29), steps_from_lowest(&graph, graph.shortest_paths()));
+        assert_eq!(Some(29), part2(&EXAMPLE));
+    }
+
+    #[test]
+    pub fn rendering() {
+        let graph = parse_grid(&EXAMPLE).unwrap();
+        let paths = graph.shortest_paths();
+        let route = render_route(&graph, paths, graph.start).unwrap();
+        assert_eq!(
+            ">>vv<<<<
+abvvv<<^
+acvv>E^^
+acv>>>^^
+ab>>>>>^",
+            route
+        );
+        let arrows = |s: &str| s.chars().filter(|c| "<>^v".contains(*c)).count();
+        assert_eq!(31, arrows(&route));
+
+        let from = closest_lowest(&graph, paths).unwrap();
+        assert_eq!(29, arrows(&render_route(&graph, paths, from).unwrap()));
+    }
+
+    #[test]
+    pub fn bfs_matches_dijkstra() {
+        let graph = parse_grid(&EXAMPLE).unwrap();
+        assert_eq!(graph.dijkstra().distance, graph.bfs().distance);
+    }
+
+    #[test]
+    pub fn parse_errors() {
+        let error = parse_grid("Sab\nabéE").unwrap_err();
+        assert_eq!((2, 3, "é"), (error.line, error.column, error.text.as_str()));
+        let error = parse_grid("Sab\nabc").unwrap_err();
+        assert_eq!("a peak tile `E`", error.expected);
+    }
+
+    #[test]
+    pub fn rule_steps() {
+        let rules = Rules {
+            max_descent: Some(2),
+            impassable: vec![b'#'],
+            passable: vec![b'.'],
+            ..Default::default()
+        };
+        assert_eq!(Some(1), rules.step(b'a', b'b'));
+        assert_eq!(None, rules.step(b'a', b'c'));
+        assert_eq!(Some(1), rules.step(b'e', b'c'));
+        assert_eq!(None, rules.step(b'e', b'b'));
+        assert_eq!(None, rules.step(b'a', b'#'));
+        assert_eq!(Some(1), rules.step(b'.', b'z'));
+    }
+
+    const TILES: &str = "
+S.#
+#..
+##E
+";
+
+    #[test]
+    pub fn passable_and_diagonal() {
+        let steps = |rules: &Rules| {
+            let graph = parse_grid_with(TILES, rules).unwrap();
+            steps_from_start(&graph, graph.shortest_paths())
+        };
+        let mut rules = Rules {
+            impassable: vec![b'#'],
+            ..Default::default()
+        };
+        assert_eq!(None, steps(&rules));
+        let graph = parse_grid_with(TILES, &rules).unwrap();
+        let paths = graph.shortest_paths();
+        assert_eq!(None, steps_from_lowest(&graph, paths));
+        assert_eq!(None, Day::render(&graph, Part::One));
+        assert_eq!(Answer::from("unreachable"), Day::part1(&graph));
+        assert_eq!(Answer::from("unreachable"), Day::part2(&graph));
+        rules.passable = vec![b'.'];
+        assert_eq!(Some(4), steps(&rules));
+        rules.diagonal = true;
+        assert_eq!(Some(2), steps(&rules));
+
+        let graph = parse_grid_with(TILES, &rules).unwrap();
+        let paths = graph.shortest_paths();
+        assert_eq!(
+            Some("↘.#\n#↘.\n##E".to_string()),
+            render_route(&graph, paths, graph.start)
+        );
+    }
+
+    #[test]
+    pub fn step_costs() {
+        let rules = Rules {
+            max_climb: 25,
+            step_cost: |rise| 1 + rise.max(0) as u32,
+            ..Default::default()
+        };
+        let graph = parse_grid_with("SbcE\naaaa", &rules).unwrap();
+        let paths = graph.shortest_paths();
+        assert_eq!(Some(28), steps_from_start(&graph, paths));
+        assert_eq!(Some(3), graph.bfs().distance(graph.start));
+        assert_eq!(
+            Some(vec![graph.start, 1, 2, graph.peak]),
+            route_to_peak(paths, graph.start)
+        );
     }
 }
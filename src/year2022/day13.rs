@@ -1,48 +1,55 @@
-use std::{
-    collections::VecDeque,
-    fmt::{Debug, Display},
-};
+use std::fmt::{Debug, Display};
 
 use itertools::Itertools;
 
+use crate::{
+    provider,
+    solution::{Answer, Solution},
+    util::{ParseError, parse, scan},
+};
+
 #[derive(Eq, PartialEq, Clone)]
-enum Value {
+pub enum Value {
     Integer(u32),
     List(Vec<Value>),
 }
 
+/// The first character of `s`, or nothing at its end.
+fn first_char(s: &str) -> &str {
+    &s[..s.chars().next().map_or(0, char::len_utf8)]
+}
+
 impl Value {
-    fn parse(str_bytes: &[u8]) -> Result<Self, ()> {
-        if str_bytes.iter().next() == Some(&b'[') {
-            let mut unclosed = 0;
-            let mut indices = VecDeque::new();
-            let (i_closing, _) = str_bytes
-                .iter()
-                .enumerate()
-                .find(|(i, c)| {
-                    if **c == b'[' {
-                        unclosed += 1;
-                    } else if **c == b']' {
-                        unclosed -= 1;
-                    } else if **c == b',' && unclosed == 1 {
-                        indices.push_back(*i);
-                    }
-                    unclosed == 0
-                })
-                .ok_or(())?;
-
-            indices.push_back(i_closing);
-            let mut values = Vec::new();
-            let mut left = 1;
-            while let Some(right) = indices.pop_front() {
-                if let Ok(v) = Value::parse(&str_bytes[left..i_closing]) {
-                    values.push(v);
-                }
-                left = right + 1;
+    /// Parses a whole packet line.
+    pub fn parse(s: &str) -> Result<Self, ParseError> {
+        let (value, rest) = Self::parse_prefix(s, s)?;
+        if !rest.is_empty() {
+            return Err(ParseError::at(s, first_char(rest), "the end of the packet"));
+        }
+        Ok(value)
+    }
+
+    /// Parses the value at the start of `s`, returning it with the text after it. Errors are
+    /// located within `line`.
+    fn parse_prefix<'a>(line: &str, s: &'a str) -> Result<(Self, &'a str), ParseError> {
+        let Some(mut rest) = s.strip_prefix('[') else {
+            let (n, rest) = scan::prefix(s)
+                .ok_or_else(|| ParseError::at(line, first_char(s), "an integer or a list"))?;
+            return Ok((Value::Integer(n), rest));
+        };
+
+        let mut values = Vec::new();
+        if let Some(rest) = rest.strip_prefix(']') {
+            return Ok((Value::List(values), rest));
+        }
+        loop {
+            let (value, after) = Self::parse_prefix(line, rest)?;
+            values.push(value);
+            match after.as_bytes().first() {
+                Some(b',') => rest = &after[1..],
+                Some(b']') => return Ok((Value::List(values), &after[1..])),
+                _ => return Err(ParseError::at(line, first_char(after), "`,` or `]`")),
             }
-            Ok(Value::List(values))
-        } else {
-            atoi::atoi(str_bytes).ok_or(()).map(Value::Integer)
         }
     }
 }
@@ -101,16 +108,11 @@ impl Ord for Value {
     }
 }
 
-fn parse_lines(input: &str) -> Vec<Value> {
-    input
-        .lines()
-        .filter_map(|l| Value::parse(l.as_bytes()).ok())
-        .collect()
+pub fn parse_lines(input: &str) -> Result<Vec<Value>, ParseError> {
+    parse::lines(input, Value::parse)
 }
 
-pub fn part1(input: &str) -> usize {
-    let lines = parse_lines(input);
-    // dbg!(&lines);
+fn sum_ordered_pair_indices(lines: &[Value]) -> usize {
     let correct = lines
         .iter()
         .tuples()
@@ -121,13 +123,12 @@ pub fn part1(input: &str) -> usize {
     correct.iter().sum()
 }
 
-#[allow(dead_code, unused_variables)]
-pub fn part2(input: &str) -> usize {
+fn decoder_key(lines: &[Value]) -> usize {
     let additional_packets = [
-        Value::parse(b"[[2]]").unwrap(),
-        Value::parse(b"[[6]]").unwrap(),
+        Value::parse("[[2]]").unwrap(),
+        Value::parse("[[6]]").unwrap(),
     ];
-    let mut lines = parse_lines(input);
+    let mut lines = lines.to_vec();
     lines.extend(additional_packets.iter().cloned());
     lines.sort();
     lines.iter().enumerate().fold(1, |a, (i, v)| {
@@ -139,61 +140,74 @@ pub fn part2(input: &str) -> usize {
     })
 }
 
-pub fn main() {
-    let input = std::fs::read_to_string("input/2022/day13.txt").unwrap();
-    dbg!(part1(&input));
-    dbg!(part2(&input));
+pub fn part1(input: &str) -> usize {
+    sum_ordered_pair_indices(&parse_lines(input).unwrap())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub fn part2(input: &str) -> usize {
+    decoder_key(&parse_lines(input).unwrap())
+}
 
-    const EXAMPLE: &str = r"[1,1,3,1,1]
-[1,1,5,1,1]
+pub struct Day;
 
-[[1],[2,3,4]]
-[[1],4]
+impl Solution for Day {
+    type Parsed<'a> = Vec<Value>;
 
-[9]
-[[8,7,6]]
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse_lines(input)
+    }
 
-[[4,4],4,4]
-[[4,4],4,4,4]
+    fn part1(lines: &Self::Parsed<'_>) -> Answer {
+        sum_ordered_pair_indices(lines).into()
+    }
 
-[7,7,7,7]
-[7,7,7]
+    fn part2(lines: &Self::Parsed<'_>) -> Answer {
+        decoder_key(lines).into()
+    }
+}
 
-[]
-[3]
+pub fn main() -> Result<(), provider::Error> {
+    let input = provider::input(2022, 13)?;
+    dbg!(part1(&input));
+    dbg!(part2(&input));
+    Ok(())
+}
 
-[[[]]]
-[[]]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples;
+    use once_cell::sync::Lazy;
 
-[1,[2,[3,[4,[5,6,7]]]],8,9]
-[1,[2,[3,[4,[5,6,0]]]],8,9]";
+    static EXAMPLE: Lazy<String> = Lazy::new(|| examples::input(2022, 13, 1));
 
     #[test]
     pub fn parsing() {
-        let lines = parse_lines(EXAMPLE);
+        let lines = parse_lines(&EXAMPLE).unwrap();
 
         assert_eq!(
-            Value::parse(b"[1,[2,[3,[4,[5,6,7]]]],8,9]").unwrap(),
+            Value::parse("[1,[2,[3,[4,[5,6,7]]]],8,9]").unwrap(),
             lines[14]
         );
         assert_eq!(
-            Value::parse(b"[1,[2,[3,[4,[5,6,0]]]],8,9]").unwrap(),
+            Value::parse("[1,[2,[3,[4,[5,6,0]]]],8,9]").unwrap(),
             lines[15]
         );
+        assert_eq!(Ok(Value::List(vec![])), Value::parse("[]"));
+
+        let error = parse_lines("[1,2]\n\n[1,[2 3]]").unwrap_err();
+        assert_eq!((3, 6, " "), (error.line, error.column, error.text.as_str()));
+        let error = Value::parse("[1]]").unwrap_err();
+        assert_eq!((4, "the end of the packet"), (error.column, error.expected.as_str()));
     }
 
     #[test]
     pub fn part1_example() {
-        assert_eq!(13, part1(EXAMPLE));
+        assert_eq!(13, part1(&EXAMPLE));
     }
 
     #[test]
     pub fn part2_example() {
-        assert_eq!(140, part2(EXAMPLE));
+        assert_eq!(140, part2(&EXAMPLE));
     }
 }
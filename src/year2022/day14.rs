@@ -1,33 +1,40 @@
 use std::{
     fmt::Display,
-    ops::{Index, IndexMut},
+    ops::{Deref, DerefMut},
     str::FromStr,
 };
 
 use itertools::Itertools;
-use once_cell::sync::Lazy;
-use regex::Regex;
+
+use crate::{
+    provider,
+    solution::{Answer, Solution},
+    util::{Grid, ParseError, parse, scan},
+};
 
 const SAND_FROM: (usize, usize) = (500, 0);
-struct Grid<const WIDTH: usize, const HEIGHT: usize> {
-    cells: Vec<bool>,
-}
+const WIDTH: usize = 1000;
+const HEIGHT: usize = 200;
+
+/// Cells blocked by rock or settled sand.
+#[derive(Clone)]
+pub struct Cave(Grid<bool>);
 
-impl<const WIDTH: usize, const HEIGHT: usize> Index<(usize, usize)> for Grid<WIDTH, HEIGHT> {
-    type Output = bool;
+impl Deref for Cave {
+    type Target = Grid<bool>;
 
-    fn index(&self, index: (usize, usize)) -> &Self::Output {
-        &self.cells[index.1 * WIDTH + index.0]
+    fn deref(&self) -> &Self::Target {
+        &self.0
     }
 }
 
-impl<const WIDTH: usize, const HEIGHT: usize> IndexMut<(usize, usize)> for Grid<WIDTH, HEIGHT> {
-    fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
-        &mut self.cells[index.1 * WIDTH + index.0]
+impl DerefMut for Cave {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
     }
 }
 
-impl<const WIDTH: usize, const HEIGHT: usize> Display for Grid<WIDTH, HEIGHT> {
+impl Display for Cave {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         const INTERESTING_WIDTH: usize = 500;
         const INTERESTING_HEIGHT: usize = 175;
@@ -45,10 +52,38 @@ impl<const WIDTH: usize, const HEIGHT: usize> Display for Grid<WIDTH, HEIGHT> {
     }
 }
 
-static ROCK_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?-u)(\d+),(\d+)").unwrap());
+fn parse_point(s: &str) -> Result<(usize, usize), ParseError> {
+    let [x, y] = scan::fields(s, &["", ",", ""])
+        .ok_or_else(|| ParseError::new(s, "a point like 498,4"))?;
+    let point = (x, y);
+    // the bottom two rows are left for the floor
+    if point.0 >= WIDTH || point.1 >= HEIGHT - 2 {
+        return Err(ParseError::new(
+            s,
+            format!("a point inside the {WIDTH}x{} cave", HEIGHT - 2),
+        ));
+    }
+    Ok(point)
+}
 
-impl<const WIDTH: usize, const HEIGHT: usize> FromStr for Grid<WIDTH, HEIGHT> {
-    type Err = ();
+/// Parses a line of rock path points, each in line with the one before.
+fn parse_path(line: &str) -> Result<Vec<(usize, usize)>, ParseError> {
+    let mut path: Vec<(usize, usize)> = Vec::new();
+    for p in line.split(" -> ") {
+        let point = parse_point(p).map_err(|e| e.within(line, p))?;
+        if let Some(&last) = path.last()
+            && last.0 != point.0
+            && last.1 != point.1
+        {
+            return Err(ParseError::at(line, p, "a point in line with the previous one"));
+        }
+        path.push(point);
+    }
+    Ok(path)
+}
+
+impl FromStr for Cave {
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         fn enumerate_line(
@@ -65,46 +100,38 @@ impl<const WIDTH: usize, const HEIGHT: usize> FromStr for Grid<WIDTH, HEIGHT> {
                 Box::new(std::iter::empty())
             }
         }
-        let mut grid = Grid::<WIDTH, HEIGHT> {
-            cells: vec![false; WIDTH * HEIGHT],
-        };
-        let coords = s.lines().flat_map(|l| {
-            ROCK_REGEX
-                .captures_iter(l)
-                .filter_map(|c| {
-                    c[1].parse()
-                        .and_then(|x: usize| c[2].parse().map(|y: usize| (x, y)))
-                        .ok()
-                })
-                .tuple_windows()
-                .flat_map(|(l, r)| enumerate_line(l, r))
-        });
-
-        for c in coords {
-            grid[c] = true;
+        let paths = parse::lines(s, parse_path)?;
+        if paths.is_empty() {
+            return Err(ParseError::at(s, &s[s.len()..], "a rock path"));
+        }
+        let mut cave = Cave(Grid::new(WIDTH, HEIGHT, false));
+        for path in paths {
+            for (from, to) in path.into_iter().tuple_windows() {
+                for c in enumerate_line(from, to) {
+                    cave[c] = true;
+                }
+            }
         }
-        Ok(grid)
+        Ok(cave)
     }
 }
 
-impl<const WIDTH: usize, const HEIGHT: usize> Grid<WIDTH, HEIGHT> {
+impl Cave {
     pub fn create_floor(&mut self) {
-        let y_height = (0..WIDTH)
-            .cartesian_product(0..HEIGHT)
-            .filter_map(|c| if self[c] { Some(c.1) } else { None })
+        let y_height = self
+            .enumerate()
+            .filter_map(|(c, &rock)| if rock { Some(c.1) } else { None })
             .max()
-            .expect("non-empty")
+            .expect("parsing rejects caves without rock")
             + 2;
 
-        for x in 0..WIDTH {
-            self[(x, y_height)] = true;
-        }
+        self.row_mut(y_height).fill(true);
     }
 
     pub fn place_sand(&mut self) -> bool {
         let mut c = SAND_FROM;
         loop {
-            if c.1 >= HEIGHT - 1 || c.0 == 0 || c.0 >= WIDTH - 1 {
+            if c.1 >= self.height() - 1 || c.0 == 0 || c.0 >= self.width() - 1 {
                 break false;
             }
             if !self[(c.0, c.1 + 1)] {
@@ -154,33 +181,55 @@ impl<const WIDTH: usize, const HEIGHT: usize> Grid<WIDTH, HEIGHT> {
 }
 
 pub fn part1(input: &str) -> usize {
-    let mut grid: Grid<1000, 200> = input.parse().unwrap();
+    let mut grid: Cave = input.parse().unwrap();
     grid.place_until_settled(false)
 }
 
 #[allow(dead_code, unused_variables)]
 pub fn part2(input: &str) -> usize {
-    let mut grid: Grid<1000, 200> = input.parse().unwrap();
+    let mut grid: Cave = input.parse().unwrap();
     grid.create_floor();
     grid.place_until_full(false)
 }
 
-pub fn main() {
-    let input = std::fs::read_to_string("input/2022/day14.txt").unwrap();
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed<'a> = Cave;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        input.parse()
+    }
+
+    fn part1(grid: &Self::Parsed<'_>) -> Answer {
+        grid.clone().place_until_settled(false).into()
+    }
+
+    fn part2(grid: &Self::Parsed<'_>) -> Answer {
+        let mut grid = grid.clone();
+        grid.create_floor();
+        grid.place_until_full(false).into()
+    }
+}
+
+pub fn main() -> Result<(), provider::Error> {
+    let input = provider::input(2022, 14)?;
     dbg!(part1(&input));
     dbg!(part2(&input));
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples;
+    use once_cell::sync::Lazy;
 
-    const EXAMPLE: &str = r"498,4 -> 498,6 -> 496,6
-503,4 -> 502,4 -> 502,9 -> 494,9";
+    static EXAMPLE: Lazy<String> = Lazy::new(|| examples::input(2022, 14, 1));
 
     #[test]
     pub fn parsing() {
-        let grid: Grid<1000, 200> = EXAMPLE.parse().unwrap();
+        let grid: Cave = EXAMPLE.parse().unwrap();
         assert!(grid[(498, 4)]);
         assert!(grid[(498, 5)]);
         assert!(grid[(498, 6)]);
@@ -188,11 +237,38 @@ mod tests {
         assert!(grid[(496, 6)]);
         assert!(grid[(503, 4)]);
         assert!(grid[(502, 4)]);
+
+        let error = "498,4 -> 498,6\n503,4 -> 1503,4".parse::<Cave>().err().unwrap();
+        assert_eq!((2, 10, "1503,4"), (error.line, error.column, error.text.as_str()));
+        let error = "498,4 -> 500,6".parse::<Cave>().err().unwrap();
+        assert_eq!("a point in line with the previous one", error.expected);
+        let error = "498;4 -> 498,6".parse::<Cave>().err().unwrap();
+        assert_eq!(
+            (1, 1, "498;4", "a point like 498,4"),
+            (
+                error.line,
+                error.column,
+                error.text.as_str(),
+                error.expected.as_str()
+            )
+        );
+        assert!("498,4,5 -> 498,6".parse::<Cave>().is_err());
+
+        let error = "498,198 -> 500,198".parse::<Cave>().err().unwrap();
+        assert_eq!(
+            (1, 1, "a point inside the 1000x198 cave"),
+            (error.line, error.column, error.expected.as_str())
+        );
+        let mut grid: Cave = "498,197 -> 500,197".parse().unwrap();
+        grid.create_floor();
+        assert!(grid[(0, HEIGHT - 1)]);
+        let error = "".parse::<Cave>().err().unwrap();
+        assert_eq!("a rock path", error.expected);
     }
 
     #[test]
     pub fn placing_sand() {
-        let mut grid: Grid<1000, 200> = EXAMPLE.parse().unwrap();
+        let mut grid: Cave = EXAMPLE.parse().unwrap();
         assert!(!grid[(500, 8)]);
         assert!(grid.place_sand());
         assert!(grid[(500, 8)]);
@@ -208,11 +284,11 @@ mod tests {
 
     #[test]
     pub fn part1_example() {
-        assert_eq!(24, part1(EXAMPLE));
+        assert_eq!(24, part1(&EXAMPLE));
     }
 
     #[test]
     pub fn part2_example() {
-        assert_eq!(93, part2(EXAMPLE));
+        assert_eq!(93, part2(&EXAMPLE));
     }
 }
@@ -3,6 +3,12 @@ use std::{
     str::FromStr,
 };
 
+use crate::{
+    provider,
+    solution::{Answer, Solution},
+    util::{ParseError, parse},
+};
+
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub enum Shape {
     Rock = 1,
@@ -23,14 +29,11 @@ pub struct Stratagem {
     pub response: Shape,
 }
 
-pub fn parse<F>(input: &str, from_str: F) -> Vec<Stratagem>
+pub fn parse<F>(input: &str, from_str: F) -> Result<Vec<Stratagem>, ParseError>
 where
-    F: Fn(&str) -> Result<Stratagem, ()>,
+    F: Fn(&str) -> Result<Stratagem, ParseError>,
 {
-    input
-        .lines()
-        .map(|l| from_str(l).expect("line should be in expected format"))
-        .collect()
+    parse::lines(input, from_str)
 }
 
 pub fn calculate_score(input: &[Stratagem]) -> u64 {
@@ -40,31 +43,60 @@ pub fn calculate_score(input: &[Stratagem]) -> u64 {
 }
 
 pub fn part1(input: &str) -> u64 {
-    calculate_score(&parse(input, Stratagem::from_str_part1))
+    calculate_score(&parse(input, Stratagem::from_str_part1).unwrap())
 }
 
 pub fn part2(input: &str) -> u64 {
-    calculate_score(&parse(input, Stratagem::from_str_part2))
+    calculate_score(&parse(input, Stratagem::from_str_part2).unwrap())
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    // The two parts read the second column differently, so both readings are parsed up front
+    type Parsed<'a> = (Vec<Stratagem>, Vec<Stratagem>);
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        Ok((
+            parse(input, Stratagem::from_str_part1)?,
+            parse(input, Stratagem::from_str_part2)?,
+        ))
+    }
+
+    fn part1((stratagems, _): &Self::Parsed<'_>) -> Answer {
+        calculate_score(stratagems).into()
+    }
+
+    fn part2((_, stratagems): &Self::Parsed<'_>) -> Answer {
+        calculate_score(stratagems).into()
+    }
 }
 
-pub fn main() {
-    let input = std::fs::read_to_string("input/2022/day2.txt").unwrap();
+pub fn main() -> Result<(), provider::Error> {
+    let input = provider::input(2022, 2)?;
     let score = part2(&input);
     println!("{score}");
+    Ok(())
+}
+
+/// The line's two columns, parsed as `L` and `R`.
+fn columns<L: FromStr<Err = ParseError>, R: FromStr<Err = ParseError>>(
+    s: &str,
+) -> Result<(L, R), ParseError> {
+    let (left, right) = parse::split_once(s, " ", "two columns")?;
+    let left = left.parse().map_err(|e: ParseError| e.within(s, left))?;
+    let right = right.parse().map_err(|e: ParseError| e.within(s, right))?;
+    Ok((left, right))
 }
 
 impl Stratagem {
-    fn from_str_part1(s: &str) -> Result<Self, ()> {
-        let mut parts = s.split_whitespace();
-        let opponent = parts.next().ok_or(())?.parse()?;
-        let response = parts.next().ok_or(())?.parse()?;
+    fn from_str_part1(s: &str) -> Result<Self, ParseError> {
+        let (opponent, response) = columns(s)?;
         Ok(Stratagem { opponent, response })
     }
 
-    fn from_str_part2(s: &str) -> Result<Self, ()> {
-        let mut parts = s.split_whitespace();
-        let opponent = parts.next().ok_or(())?.parse()?;
-        let outcome: Outcome = parts.next().ok_or(())?.parse()?;
+    fn from_str_part2(s: &str) -> Result<Self, ParseError> {
+        let (opponent, outcome): (Shape, Outcome) = columns(s)?;
 
         let response = match outcome {
             Outcome::Lose => match opponent {
@@ -85,27 +117,27 @@ impl Stratagem {
 }
 
 impl FromStr for Shape {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "A" | "X" => Ok(Shape::Rock),
             "B" | "Y" => Ok(Shape::Paper),
             "C" | "Z" => Ok(Shape::Scissors),
-            _ => Err(()),
+            _ => Err(ParseError::new(s, "a shape (A, B, C, X, Y or Z)")),
         }
     }
 }
 
 impl FromStr for Outcome {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "X" => Ok(Outcome::Lose),
             "Y" => Ok(Outcome::Draw),
             "Z" => Ok(Outcome::Win),
-            _ => Err(()),
+            _ => Err(ParseError::new(s, "an outcome (X, Y or Z)")),
         }
     }
 }
@@ -129,21 +161,19 @@ impl Shape {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples;
+    use once_cell::sync::Lazy;
+
+    static EXAMPLE: Lazy<String> = Lazy::new(|| examples::input(2022, 2, 1));
 
     #[test]
     pub fn example_part1() {
-        let input = r"A Y
-B X
-C Z";
-        assert_eq!(15, part1(input));
+        assert_eq!(15, part1(&EXAMPLE));
     }
 
     #[test]
     pub fn example_part2() {
-        let input = r"A Y
-B X
-C Z";
-        assert_eq!(12, part2(input));
+        assert_eq!(12, part2(&EXAMPLE));
     }
 
     #[test]
@@ -1,5 +1,11 @@
 use std::{collections::HashSet, str::FromStr};
 
+use crate::{
+    provider,
+    solution::{Answer, Solution},
+    util::{ParseError, parse},
+};
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
 pub struct Item(u8);
@@ -27,59 +33,66 @@ impl Rucksack {
 }
 
 impl FromStr for Rucksack {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let bytes = s.as_bytes();
-        let size = bytes.len() / 2;
+        if !s.len().is_multiple_of(2) {
+            return Err(ParseError::new(s, "an even number of items"));
+        }
+        let size = s.len() / 2;
 
         Ok(Self {
-            first_half: parse_items(&bytes[..size]),
-            second_half: parse_items(&bytes[size..]),
+            first_half: parse_items(s, 0..size)?,
+            second_half: parse_items(s, size..s.len())?,
         })
     }
 }
 
-fn parse_items<'a>(bytes: impl IntoIterator<Item = &'a u8>) -> HashSet<Item> {
-    bytes.into_iter().map(|b| Item::from_ascii(*b)).collect()
+fn parse_items(s: &str, range: std::ops::Range<usize>) -> Result<HashSet<Item>, ParseError> {
+    range
+        .map(|i| {
+            let item = s
+                .get(i..i + 1)
+                .ok_or_else(|| ParseError::new(s, "ASCII items"))?;
+            item.parse().map_err(|e: ParseError| e.within(s, item))
+        })
+        .collect()
 }
 
 impl FromStr for Item {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.is_ascii() {
-            Ok(Self::from_ascii(s.as_bytes()[0]))
-        } else {
-            Err(())
+        match s.as_bytes() {
+            &[a] => Self::from_ascii(a),
+            _ => None,
         }
+        .ok_or_else(|| ParseError::new(s, "an item letter"))
     }
 }
 
 impl Item {
-    pub fn from_ascii(a: u8) -> Self {
-        if a <= b'Z' {
-            Self(a.checked_sub(38).unwrap())
-        } else {
-            Self(a.checked_sub(96).unwrap())
+    pub fn from_ascii(a: u8) -> Option<Self> {
+        match a {
+            b'a'..=b'z' => Some(Self(a - 96)),
+            b'A'..=b'Z' => Some(Self(a - 38)),
+            _ => None,
         }
     }
 }
 
-pub fn part1(input: &str) -> u64 {
-    input
-        .lines()
-        .map(|l| l.parse().expect("input in correct format"))
-        .map(|s: Rucksack| u64::from(s.misplaced_item().expect("always 1 misplaced").0))
-        .sum()
+pub fn parse_rucksacks(input: &str) -> Result<Vec<Rucksack>, ParseError> {
+    parse::lines(input, str::parse)
 }
 
-pub fn part2(input: &str) -> u64 {
-    let sacks: Vec<Rucksack> = input
-        .lines()
-        .map(|l| l.parse().expect("input in correct format"))
-        .collect();
+fn misplaced_priorities(sacks: &[Rucksack]) -> u64 {
+    sacks
+        .iter()
+        .map(|s| u64::from(s.misplaced_item().expect("always 1 misplaced").0))
+        .sum()
+}
 
+fn badge_priorities(sacks: &[Rucksack]) -> u64 {
     sacks
         .chunks_exact(3)
         .map(|c| c[0].items().find(|i| c[1].contains(i) && c[2].contains(i)))
@@ -87,14 +100,43 @@ pub fn part2(input: &str) -> u64 {
         .sum()
 }
 
-pub fn main() {
-    let input = std::fs::read_to_string("input/2022/day3.txt").unwrap();
+pub fn part1(input: &str) -> u64 {
+    misplaced_priorities(&parse_rucksacks(input).unwrap())
+}
+
+pub fn part2(input: &str) -> u64 {
+    badge_priorities(&parse_rucksacks(input).unwrap())
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed<'a> = Vec<Rucksack>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse_rucksacks(input)
+    }
+
+    fn part1(sacks: &Self::Parsed<'_>) -> Answer {
+        misplaced_priorities(sacks).into()
+    }
+
+    fn part2(sacks: &Self::Parsed<'_>) -> Answer {
+        badge_priorities(sacks).into()
+    }
+}
+
+pub fn main() -> Result<(), provider::Error> {
+    let input = provider::input(2022, 3)?;
     dbg!(part2(&input));
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples;
+    use once_cell::sync::Lazy;
 
     #[test]
     pub fn priority() {
@@ -109,7 +151,7 @@ mod tests {
 
     macro_rules! assert_sack_contains {
         ($expected: literal, $half: expr) => {
-            assert!($half.contains(&Item::from_ascii($expected[0])));
+            assert!($half.contains(&Item::from_ascii($expected[0]).unwrap()));
         };
     }
 
@@ -132,9 +174,22 @@ mod tests {
         assert_sack_contains!(b"p", sack.second_half);
     }
 
+    #[test]
+    pub fn rucksack_errors() {
+        let error = "vJ!w".parse::<Rucksack>().err().unwrap();
+        assert_eq!((1, 3, "!"), (error.line, error.column, error.text.as_str()));
+        assert!("vJw".parse::<Rucksack>().is_err());
+
+        let error = parse_rucksacks("vJrw\nab1c").err().unwrap();
+        assert_eq!((2, 3), (error.line, error.column));
+    }
+
     macro_rules! assert_misplaced_item {
         ($expected: literal, $sack: literal) => {
-            assert_eq!(Item::from_ascii($expected[0]), get_misplaced_item(&$sack));
+            assert_eq!(
+                Item::from_ascii($expected[0]),
+                Some(get_misplaced_item(&$sack))
+            );
         };
     }
 
@@ -153,22 +208,17 @@ mod tests {
         sack.misplaced_item().unwrap()
     }
 
-    const EXAMPLE_SACKS: &str = r"vJrwpWtwJgWrhcsFMMfFFhFp
-jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
-PmmdzqPrVvPwwTWBwg
-wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
-ttgJtRGJQctTZtZT
-CrZsJsPPZsGzwwsLwLmpwMDw";
+    static EXAMPLE_SACKS: Lazy<String> = Lazy::new(|| examples::input(2022, 3, 1));
 
     #[test]
     pub fn part1_example() {
-        let result = part1(EXAMPLE_SACKS);
+        let result = part1(&EXAMPLE_SACKS);
         assert_eq!(157, result);
     }
 
     #[test]
     pub fn part2_example() {
-        let result = part2(EXAMPLE_SACKS);
+        let result = part2(&EXAMPLE_SACKS);
         assert_eq!(70, result);
     }
 }
@@ -1,5 +1,11 @@
 use std::{ops::RangeInclusive, str::FromStr};
 
+use crate::{
+    provider,
+    solution::{Answer, Solution},
+    util::{ParseError, parse},
+};
+
 pub struct PairAssignments {
     pub left: RangeInclusive<u64>,
     pub right: RangeInclusive<u64>,
@@ -23,41 +29,71 @@ fn overlap(left: &RangeInclusive<u64>, right: &RangeInclusive<u64>) -> bool {
     }
 }
 
-pub fn part1(input: &str) -> usize {
-    input
-        .lines()
-        .map(|l| PairAssignments::from_str(l).expect("line format"))
+pub fn parse_pairs(input: &str) -> Result<Vec<PairAssignments>, ParseError> {
+    parse::lines(input, PairAssignments::from_str)
+}
+
+fn count_contained(pairs: &[PairAssignments]) -> usize {
+    pairs
+        .iter()
         .filter(|pair| contains_symmetric(&pair.left, &pair.right))
         .count()
 }
 
-pub fn part2(input: &str) -> usize {
-    input
-        .lines()
-        .map(|l| PairAssignments::from_str(l).expect("line format"))
+fn count_overlapping(pairs: &[PairAssignments]) -> usize {
+    pairs
+        .iter()
         .filter(|pair| overlap(&pair.left, &pair.right))
         .count()
 }
 
-pub fn main() {
-    let input = std::fs::read_to_string("input/2022/day4.txt").unwrap();
+pub fn part1(input: &str) -> usize {
+    count_contained(&parse_pairs(input).unwrap())
+}
+
+pub fn part2(input: &str) -> usize {
+    count_overlapping(&parse_pairs(input).unwrap())
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed<'a> = Vec<PairAssignments>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse_pairs(input)
+    }
+
+    fn part1(pairs: &Self::Parsed<'_>) -> Answer {
+        count_contained(pairs).into()
+    }
+
+    fn part2(pairs: &Self::Parsed<'_>) -> Answer {
+        count_overlapping(pairs).into()
+    }
+}
+
+pub fn main() -> Result<(), provider::Error> {
+    let input = provider::input(2022, 4)?;
     dbg!(part1(&input));
     dbg!(part2(&input));
+    Ok(())
+}
+
+fn parse_range(s: &str) -> Result<RangeInclusive<u64>, ParseError> {
+    let (start, end) = parse::split_once(s, "-", "a range like 2-4")?;
+    let bound = |b| parse::number(b, "a section number").map_err(|e| e.within(s, b));
+    Ok(bound(start)?..=bound(end)?)
 }
 
 impl FromStr for PairAssignments {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let splits: Vec<u64> = s
-            .split([',', '-'])
-            .map(|c| c.parse().expect("pair input format"))
-            .collect();
-
-        let mut iter = splits.chunks(2).map(|c| RangeInclusive::new(c[0], c[1]));
+        let (left, right) = parse::split_once(s, ",", "two ranges separated by a comma")?;
         Ok(Self {
-            left: iter.next().unwrap(),
-            right: iter.next().unwrap(),
+            left: parse_range(left).map_err(|e| e.within(s, left))?,
+            right: parse_range(right).map_err(|e| e.within(s, right))?,
         })
     }
 }
@@ -65,21 +101,18 @@ impl FromStr for PairAssignments {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples;
+    use once_cell::sync::Lazy;
 
-    const EXAMPLE: &str = r"2-4,6-8
-2-3,4-5
-5-7,7-9
-2-8,3-7
-6-6,4-6
-2-6,4-8";
+    static EXAMPLE: Lazy<String> = Lazy::new(|| examples::input(2022, 4, 1));
 
     #[test]
     pub fn part1_example() {
-        assert_eq!(2, part1(EXAMPLE));
+        assert_eq!(2, part1(&EXAMPLE));
     }
 
     #[test]
     pub fn part2_example() {
-        assert_eq!(4, part2(EXAMPLE));
+        assert_eq!(4, part2(&EXAMPLE));
     }
 }
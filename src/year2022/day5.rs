@@ -1,3 +1,9 @@
+use crate::{
+    provider,
+    solution::{Answer, Solution},
+    util::{ParseError, parse, scan},
+};
+
 type Item = u8;
 
 #[derive(PartialEq, Eq, Debug)]
@@ -14,64 +20,94 @@ impl Instruction {
     }
 }
 
-pub fn parse_crates(input: &str) -> Vec<Vec<Item>> {
-    let unparsed_lines: Vec<_> = input
-        .lines()
-        .skip_while(|l| l.is_empty())
-        .take_while(|l| !l.is_empty())
-        .collect();
-    let n_stacks = unparsed_lines
+pub fn parse_crates(input: &str) -> Result<Vec<Vec<Item>>, ParseError> {
+    let unparsed_lines: Vec<_> = parse::split_paragraphs(input)
+        .next()
+        .map_or(Vec::new(), |drawing| drawing.lines().collect());
+    let header = unparsed_lines
         .last()
-        .expect("input non-empty")
+        .ok_or_else(|| ParseError::new(input, "a drawing of stacks"))?;
+    let last_stack = header
         .split_ascii_whitespace()
         .last()
-        .expect("stack headers separated by spaces")
-        .parse()
-        .expect("stack headers are ints");
+        .ok_or_else(|| ParseError::at(input, header, "stack numbers"))?;
+    let n_stacks =
+        parse::number(last_stack, "a stack number").map_err(|e| e.within(input, last_stack))?;
     let mut stacks = vec![Vec::with_capacity(unparsed_lines.len()); n_stacks];
 
     for (n, stack) in stacks.iter_mut().enumerate().take(n_stacks) {
         let i_horizontal = (n * 4) + 1;
         for i_line in (0..unparsed_lines.len() - 1).rev() {
-            let item = unparsed_lines[i_line].as_bytes()[i_horizontal];
-            if item != b' ' {
-                stack.push(item);
+            // lines may stop early when the last stacks are short
+            match unparsed_lines[i_line].as_bytes().get(i_horizontal) {
+                Some(b' ') | None => {}
+                Some(&item) => stack.push(item),
             }
         }
     }
 
-    stacks
+    Ok(stacks)
 }
 
-pub fn parse_instructions(input: &str) -> Vec<Instruction> {
-    let unparsed_lines: Vec<_> = input
-        .lines()
-        .skip_while(|l| l.is_empty()) // allow extra newline at start
-        .skip_while(|l| !l.is_empty()) // crates
-        .filter(|l| !l.is_empty())
-        .collect();
-
-    unparsed_lines
-        .iter()
-        .map(|l| {
-            l.chars()
-                .filter(|c| c.is_ascii_digit() || c.is_whitespace())
-                .collect::<String>()
-        })
-        .map(|s| {
-            let mut numbers = s.split_ascii_whitespace().map(|n| n.parse().unwrap());
-            Instruction {
-                count: numbers.next().unwrap(),
-                from: numbers.next().unwrap(),
-                to: numbers.next().unwrap(),
-            }
-        })
-        .collect()
+/// Parses a `move` line for stacks that are `heights` crates high, numbered from 1, and moves
+/// the crates on `heights` so that later lines are checked against the stacks they leave.
+fn parse_instruction(line: &str, heights: &mut [usize]) -> Result<Instruction, ParseError> {
+    let instruction = parse_move(line)?;
+    let stacks = heights.len();
+    for (stack, word) in [(instruction.from, 3), (instruction.to, 5)] {
+        if !(1..=stacks).contains(&stack) {
+            let text = line.split_ascii_whitespace().nth(word).unwrap_or(line);
+            return Err(ParseError::at(
+                line,
+                text,
+                format!("a stack from 1 to {stacks}"),
+            ));
+        }
+    }
+    let height = heights[instruction.from - 1];
+    if instruction.count > height {
+        let text = line.split_ascii_whitespace().nth(1).unwrap_or(line);
+        return Err(ParseError::at(
+            line,
+            text,
+            format!("at most the {height} crates on stack {}", instruction.from),
+        ));
+    }
+    heights[instruction.from - 1] -= instruction.count;
+    heights[instruction.to - 1] += instruction.count;
+    Ok(instruction)
 }
 
-pub fn part1(input: &str) -> String {
-    let mut crates = parse_crates(input);
-    let instructions = parse_instructions(input);
+fn parse_move(line: &str) -> Result<Instruction, ParseError> {
+    if let Some([count, from, to]) = scan::fields(line, &["move ", " from ", " to ", ""]) {
+        return Ok(Instruction { count, from, to });
+    }
+
+    // only split into words to find what is wrong
+    let words: Vec<_> = line.split(' ').collect();
+    if let ["move", count, "from", from, "to", to] = words[..]
+        && let Some(n) = [count, from, to]
+            .into_iter()
+            .find(|n| !matches!(scan::prefix::<usize>(n), Some((_, ""))))
+    {
+        return Err(ParseError::at(line, n, "a number"));
+    }
+    Err(ParseError::new(
+        line,
+        "an instruction like `move 1 from 2 to 3`",
+    ))
+}
+
+/// The instructions below the drawing, whose stacks start `heights` crates high.
+pub fn parse_instructions(input: &str, heights: &[usize]) -> Result<Vec<Instruction>, ParseError> {
+    let instructions = parse::split_paragraphs(input).nth(1).unwrap_or_default();
+    let mut heights = heights.to_vec();
+    parse::lines(instructions, |line| parse_instruction(line, &mut heights))
+        .map_err(|e| e.within(input, instructions))
+}
+
+fn move_one_at_a_time(crates: &[Vec<Item>], instructions: &[Instruction]) -> String {
+    let mut crates = crates.to_vec();
 
     for instruction in instructions.iter() {
         for _ in 0..instruction.count {
@@ -83,9 +119,8 @@ pub fn part1(input: &str) -> String {
     top_of_crates(&crates)
 }
 
-pub fn part2(input: &str) -> String {
-    let mut crates = parse_crates(input);
-    let instructions = parse_instructions(input);
+fn move_all_at_once(crates: &[Vec<Item>], instructions: &[Instruction]) -> String {
+    let mut crates = crates.to_vec();
 
     for instruction in instructions.iter() {
         let from = &mut crates[instruction.from - 1];
@@ -96,38 +131,64 @@ pub fn part2(input: &str) -> String {
     top_of_crates(&crates)
 }
 
+/// The top crate of each stack, skipping stacks that ended up empty.
 fn top_of_crates<'a>(crates: impl IntoIterator<Item = &'a Vec<Item>>) -> String {
     crates
         .into_iter()
-        .map(|c| c.last().expect("stack non empty"))
+        .filter_map(|c| c.last())
         .map(|b| char::from(*b))
         .collect()
 }
 
-pub fn main() {
-    let input = std::fs::read_to_string("input/2022/day5.txt").unwrap();
+pub fn part1(input: &str) -> String {
+    let (crates, instructions) = Day::parse(input).unwrap();
+    move_one_at_a_time(&crates, &instructions)
+}
+
+pub fn part2(input: &str) -> String {
+    let (crates, instructions) = Day::parse(input).unwrap();
+    move_all_at_once(&crates, &instructions)
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed<'a> = (Vec<Vec<Item>>, Vec<Instruction>);
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        let crates = parse_crates(input)?;
+        let heights: Vec<_> = crates.iter().map(Vec::len).collect();
+        let instructions = parse_instructions(input, &heights)?;
+        Ok((crates, instructions))
+    }
+
+    fn part1((crates, instructions): &Self::Parsed<'_>) -> Answer {
+        move_one_at_a_time(crates, instructions).into()
+    }
+
+    fn part2((crates, instructions): &Self::Parsed<'_>) -> Answer {
+        move_all_at_once(crates, instructions).into()
+    }
+}
+
+pub fn main() -> Result<(), provider::Error> {
+    let input = provider::input(2022, 5)?;
     dbg!(part1(&input));
     dbg!(part2(&input));
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples;
+    use once_cell::sync::Lazy;
 
-    const EXAMPLE: &str = r"
-    [D]    
-[N] [C]    
-[Z] [M] [P]
- 1   2   3 
-
-move 1 from 2 to 1
-move 3 from 1 to 3
-move 2 from 2 to 1
-move 1 from 1 to 2";
+    static EXAMPLE: Lazy<String> = Lazy::new(|| examples::input(2022, 5, 1));
 
     #[test]
     pub fn crate_parsing() {
-        let crates = parse_crates(EXAMPLE);
+        let crates = parse_crates(&EXAMPLE).unwrap();
         assert_eq!(3, crates.len());
 
         assert_eq!(vec![b'Z', b'N'], crates[0]);
@@ -137,22 +198,84 @@ move 1 from 1 to 2";
 
     #[test]
     pub fn instruction_parsing() {
-        let instructions = parse_instructions(EXAMPLE);
+        let instructions = parse_instructions(&EXAMPLE, &[2, 3, 1]).unwrap();
         assert_eq!(4, instructions.len());
 
         assert_eq!(Instruction::new(1, 2, 1), instructions[0]);
         assert_eq!(Instruction::new(3, 1, 3), instructions[1]);
         assert_eq!(Instruction::new(2, 2, 1), instructions[2]);
         assert_eq!(Instruction::new(1, 1, 2), instructions[3]);
+
+        let error = parse_instructions("1\n\nmove 1 from 2 to 3\nmove x from 1 to 2", &[1, 1, 1])
+            .unwrap_err();
+        assert_eq!((4, 6, "x"), (error.line, error.column, error.text.as_str()));
+        for (line, text) in [
+            ("move 1x from 2 to 3", "1x"),
+            (
+                "move 1 from 2 to 99999999999999999999",
+                "99999999999999999999",
+            ),
+            ("move 1 from 2 to 3 to 4", "move 1 from 2 to 3 to 4"),
+            ("move  1 from 2 to 3", "move  1 from 2 to 3"),
+        ] {
+            let error = parse_instructions(&format!("1\n\n{line}"), &[1, 1, 1]).unwrap_err();
+            assert_eq!((3, text), (error.line, error.text.as_str()));
+        }
+    }
+
+    #[test]
+    pub fn stack_numbers() {
+        let drawing = EXAMPLE.split("\n\n").next().unwrap();
+        for (instruction, column, text) in [
+            ("move 1 from 0 to 1", 13, "0"),
+            ("move 1 from 7 to 1", 13, "7"),
+            ("move 1 from 1 to 4", 18, "4"),
+        ] {
+            let error = Day::parse(&format!("{drawing}\n\n{instruction}\n")).unwrap_err();
+            assert_eq!(
+                (6, column, text, "a stack from 1 to 3"),
+                (
+                    error.line,
+                    error.column,
+                    error.text.as_str(),
+                    error.expected.as_str()
+                )
+            );
+        }
+    }
+
+    #[test]
+    pub fn crate_counts() {
+        let drawing = EXAMPLE.split("\n\n").next().unwrap();
+        let error = Day::parse(&format!("{drawing}\n\nmove 5 from 2 to 1\n")).unwrap_err();
+        assert_eq!(
+            (6, 6, "5", "at most the 3 crates on stack 2"),
+            (
+                error.line,
+                error.column,
+                error.text.as_str(),
+                error.expected.as_str()
+            )
+        );
+        let moves = "move 3 from 2 to 1\nmove 5 from 1 to 3\nmove 7 from 3 to 2\n";
+        let error = Day::parse(&format!("{drawing}\n\n{moves}")).unwrap_err();
+        assert_eq!(
+            (8, "at most the 6 crates on stack 3"),
+            (error.line, error.expected.as_str())
+        );
+
+        let emptied = format!("{drawing}\n\nmove 1 from 3 to 1\n");
+        assert_eq!("PD", part1(&emptied));
+        assert_eq!("PD", part2(&emptied));
     }
 
     #[test]
     pub fn part1_example() {
-        assert_eq!("CMZ", part1(EXAMPLE));
+        assert_eq!("CMZ", part1(&EXAMPLE));
     }
 
     #[test]
     pub fn part2_example() {
-        assert_eq!("MCD", part2(EXAMPLE));
+        assert_eq!("MCD", part2(&EXAMPLE));
     }
 }
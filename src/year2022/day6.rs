@@ -1,61 +1,119 @@
 use hashbag::HashBag;
 
-pub fn find_distinct(input: &str, window_size: usize) -> usize {
+use crate::{
+    provider,
+    solution::{Answer, Solution},
+    util::ParseError,
+};
+
+/// How many characters are read once the last `window_size` were all different, or `None` if
+/// that never happens.
+pub fn find_distinct(input: &str, window_size: usize) -> Option<usize> {
     let data = input.trim().as_bytes();
     let mut window = HashBag::with_capacity(window_size);
     for d in data.iter().take(window_size) {
         window.insert(*d);
     }
-    for i in window_size..data.len() {
+    for i in window_size..=data.len() {
         if window.set_len() == window_size {
-            return i;
+            return Some(i);
+        }
+        if i < data.len() {
+            window.remove(&data[i - window_size]);
+            window.insert(data[i]);
         }
-        window.remove(&data[i - window_size]);
-        window.insert(data[i]);
     }
+    None
+}
 
-    panic!("nothing found");
+/// Checks that the signal has a start-of-message marker, which also contains a start-of-packet
+/// marker.
+fn parse(input: &str) -> Result<&str, ParseError> {
+    match find_distinct(input, 14) {
+        Some(_) => Ok(input),
+        None => Err(ParseError::at(
+            input,
+            input.trim(),
+            "a signal with 14 different characters in a row",
+        )),
+    }
 }
 
 pub fn part1(input: &str) -> usize {
-    find_distinct(input, 4)
+    find_distinct(parse(input).unwrap(), 4).unwrap()
 }
 
 pub fn part2(input: &str) -> usize {
-    find_distinct(input, 14)
+    find_distinct(parse(input).unwrap(), 14).unwrap()
 }
 
-pub fn main() {
-    let input = std::fs::read_to_string("input/2022/day6.txt").unwrap();
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Answer {
+        find_distinct(input, 4).unwrap().into()
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Answer {
+        find_distinct(input, 14).unwrap().into()
+    }
+}
+
+pub fn main() -> Result<(), provider::Error> {
+    let input = provider::input(2022, 6)?;
     dbg!(part1(&input));
     dbg!(part2(&input));
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples;
+    use once_cell::sync::Lazy;
 
-    const EXAMPLE1: &str = r"mjqjpqmgbljsphdztnvjfqwrcgsmlb";
-    const EXAMPLE2: &str = r"bvwbjplbgvbhsrlpgdmjqwftvncz";
-    const EXAMPLE3: &str = r"nppdvjthqldpwncqszvftbrmjlhg";
-    const EXAMPLE4: &str = r"nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg";
-    const EXAMPLE5: &str = r"zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw";
+    static EXAMPLE1: Lazy<String> = Lazy::new(|| examples::input(2022, 6, 1));
+    static EXAMPLE2: Lazy<String> = Lazy::new(|| examples::input(2022, 6, 2));
+    static EXAMPLE3: Lazy<String> = Lazy::new(|| examples::input(2022, 6, 3));
+    static EXAMPLE4: Lazy<String> = Lazy::new(|| examples::input(2022, 6, 4));
+    static EXAMPLE5: Lazy<String> = Lazy::new(|| examples::input(2022, 6, 5));
 
     #[test]
     pub fn part1_examples() {
-        assert_eq!(7, part1(EXAMPLE1));
-        assert_eq!(5, part1(EXAMPLE2));
-        assert_eq!(6, part1(EXAMPLE3));
-        assert_eq!(10, part1(EXAMPLE4));
-        assert_eq!(11, part1(EXAMPLE5));
+        assert_eq!(7, part1(&EXAMPLE1));
+        assert_eq!(5, part1(&EXAMPLE2));
+        assert_eq!(6, part1(&EXAMPLE3));
+        assert_eq!(10, part1(&EXAMPLE4));
+        assert_eq!(11, part1(&EXAMPLE5));
     }
 
     #[test]
     pub fn part2_examples() {
-        assert_eq!(19, part2(EXAMPLE1));
-        assert_eq!(23, part2(EXAMPLE2));
-        assert_eq!(23, part2(EXAMPLE3));
-        assert_eq!(29, part2(EXAMPLE4));
-        assert_eq!(26, part2(EXAMPLE5));
+        assert_eq!(19, part2(&EXAMPLE1));
+        assert_eq!(23, part2(&EXAMPLE2));
+        assert_eq!(23, part2(&EXAMPLE3));
+        assert_eq!(29, part2(&EXAMPLE4));
+        assert_eq!(26, part2(&EXAMPLE5));
+    }
+
+    #[test]
+    pub fn missing_markers() {
+        assert_eq!(Some(4), find_distinct("abcd", 4));
+        assert_eq!(None, find_distinct("ab", 4));
+        assert_eq!(None, find_distinct("abcabc", 4));
+
+        let error = Day::parse("ab\n").unwrap_err();
+        assert_eq!(
+            (1, 1, "ab"),
+            (error.line, error.column, error.text.as_str())
+        );
+        assert!(Day::parse("abcdefghijklmn").is_ok());
+        assert!(Day::parse("abcdefghijklmm").is_err());
     }
 }
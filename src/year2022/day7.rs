@@ -1,20 +1,32 @@
-use std::collections::VecDeque;
+use std::{collections::HashMap, fmt::Display};
 
-#[derive(Debug, Clone, PartialEq)]
+use itertools::Itertools;
+use serde::Serialize;
+
+use crate::{
+    provider,
+    solution::{Answer, Solution},
+    util::{ParseError, parse},
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
 pub enum Tree {
     File(File),
     Directory(Directory),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct File {
     name: String,
     size: usize,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct Directory {
     name: String,
+    /// The total size of everything below, kept up to date as children are added
+    size: usize,
     children: Vec<Tree>,
 }
 
@@ -41,6 +53,111 @@ impl File {
             size,
         }
     }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+}
+
+/// A borrowed file or directory, so the root can be handled like any other node.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Node<'a> {
+    File(&'a File),
+    Directory(&'a Directory),
+}
+
+impl<'a> From<&'a Tree> for Node<'a> {
+    fn from(tree: &'a Tree) -> Self {
+        match tree {
+            Tree::File(f) => Node::File(f),
+            Tree::Directory(d) => Node::Directory(d),
+        }
+    }
+}
+
+impl Node<'_> {
+    pub fn name(&self) -> &str {
+        match self {
+            Node::File(f) => &f.name,
+            Node::Directory(d) => &d.name,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        match self {
+            Node::File(f) => f.size,
+            Node::Directory(d) => d.size(),
+        }
+    }
+}
+
+/// The absolute path of `name` inside the directory at `parent`.
+fn join(parent: &str, name: &str) -> String {
+    if parent.ends_with('/') {
+        format!("{parent}{name}")
+    } else {
+        format!("{parent}/{name}")
+    }
+}
+
+/// The directories under a directory, parents before their children.
+pub struct Directories<'a> {
+    stack: Vec<&'a Directory>,
+}
+
+impl<'a> Iterator for Directories<'a> {
+    type Item = &'a Directory;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let dir = self.stack.pop()?;
+        self.stack
+            .extend(dir.children.iter().rev().filter_map(|c| match c {
+                Tree::Directory(d) => Some(d),
+                Tree::File(_) => None,
+            }));
+        Some(dir)
+    }
+}
+
+/// Every node under a directory with its absolute path, parents before their children.
+pub struct Walk<'a> {
+    stack: Vec<(String, Node<'a>)>,
+}
+
+impl<'a> Iterator for Walk<'a> {
+    type Item = (String, Node<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (path, node) = self.stack.pop()?;
+        if let Node::Directory(d) = node {
+            self.stack.extend(
+                d.children
+                    .iter()
+                    .rev()
+                    .map(|child| (join(&path, child.name()), child.into())),
+            );
+        }
+        Some((path, node))
+    }
+}
+
+/// Whether `text` matches `pattern`, where `?` is any one character and `*` any run of
+/// characters within a path component, and `**` any run of characters at all.
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern {
+        [] => text.is_empty(),
+        [b'*', b'*', rest @ ..] => (0..=text.len()).any(|i| glob_match(rest, &text[i..])),
+        [b'*', rest @ ..] => (0..=text.len())
+            .take_while(|&i| i == 0 || text[i - 1] != b'/')
+            .any(|i| glob_match(rest, &text[i..])),
+        [b'?', rest @ ..] => matches!(text, [c, tail @ ..] if *c != b'/' && glob_match(rest, tail)),
+        [c, rest @ ..] => matches!(text, [t, tail @ ..] if t == c && glob_match(rest, tail)),
+    }
 }
 
 impl Directory {
@@ -48,151 +165,935 @@ impl Directory {
         Self {
             name: name.into(),
             children: Default::default(),
+            size: 0,
         }
     }
 
+    /// Adds `child`, which should be complete: later changes inside it are not counted in
+    /// [`Directory::size`].
+    pub fn push(&mut self, child: Tree) {
+        self.size += child.size();
+        self.children.push(child);
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn children(&self) -> &[Tree] {
+        &self.children
+    }
+
+    pub fn child(&self, name: &str) -> Option<&Tree> {
+        self.children.iter().find(|c| c.name() == name)
+    }
+
     pub fn size(&self) -> usize {
-        self.children.iter().map(|c| c.size()).sum()
+        self.size
     }
 
-    pub fn descendants_and_self<'a>(&'a self) -> impl Iterator<Item = &'a Self> + 'a {
-        std::iter::once(self).chain(self.descendant_directories())
+    /// The node at an absolute path such as `/a/e`, taking `self` as the root `/`.
+    pub fn get(&self, path: &str) -> Option<Node<'_>> {
+        let relative = path.strip_prefix('/')?;
+        relative
+            .split('/')
+            .filter(|name| !name.is_empty())
+            .try_fold(Node::Directory(self), |node, name| match node {
+                Node::Directory(d) => d.child(name).map(Node::from),
+                Node::File(_) => None,
+            })
     }
 
-    pub fn descendant_directories<'a>(&'a self) -> Box<dyn Iterator<Item = &'a Self> + 'a> {
-        Box::new(
-            self.children
-                .iter()
-                .filter_map(|c| {
-                    if let Tree::Directory(d) = c {
-                        Some(d.descendants_and_self())
-                    } else {
-                        None
-                    }
-                })
-                .flatten(),
-        )
+    pub fn directory(&self, path: &str) -> Option<&Directory> {
+        match self.get(path)? {
+            Node::Directory(d) => Some(d),
+            Node::File(_) => None,
+        }
+    }
+
+    pub fn file(&self, path: &str) -> Option<&File> {
+        match self.get(path)? {
+            Node::File(f) => Some(f),
+            Node::Directory(_) => None,
+        }
+    }
+
+    /// The directory holding the node at `path`, or `None` for the root itself.
+    pub fn parent(&self, path: &str) -> Option<&Directory> {
+        self.get(path)?;
+        let (parent, _) = path.trim_end_matches('/').rsplit_once('/')?;
+        self.directory(if parent.is_empty() { "/" } else { parent })
+    }
+
+    /// `self` and everything under it with absolute paths, taking `self` as the root `/`.
+    pub fn walk(&self) -> Walk<'_> {
+        Walk {
+            stack: vec![("/".to_string(), Node::Directory(self))],
+        }
+    }
+
+    /// Every directory's path and total size, children before their parents as `du` lists them.
+    pub fn du(&self) -> Vec<(String, usize)> {
+        fn visit(dir: &Directory, path: String, sizes: &mut Vec<(String, usize)>) {
+            for child in &dir.children {
+                if let Tree::Directory(d) = child {
+                    visit(d, join(&path, &d.name), sizes);
+                }
+            }
+            sizes.push((path, dir.size));
+        }
+
+        let mut sizes = Vec::new();
+        visit(self, "/".to_string(), &mut sizes);
+        sizes
+    }
+
+    /// The `n` largest directories by total size, largest first.
+    pub fn largest_directories(&self, n: usize) -> Vec<(String, usize)> {
+        let mut sizes = self.du();
+        sizes.sort_by(|(a_path, a_size), (b_path, b_size)| {
+            b_size.cmp(a_size).then_with(|| a_path.cmp(b_path))
+        });
+        sizes.truncate(n);
+        sizes
+    }
+
+    /// The files whose name matches `pattern`, or whose absolute path does if the pattern has a
+    /// `/` in it. See [`glob_match`] for the syntax.
+    pub fn find_files(&self, pattern: &str) -> Vec<(String, &File)> {
+        self.walk()
+            .filter_map(|(path, node)| match node {
+                Node::File(f) => Some((path, f)),
+                Node::Directory(_) => None,
+            })
+            .filter(|(path, f)| {
+                let subject = if pattern.contains('/') { path } else { &f.name };
+                glob_match(pattern.as_bytes(), subject.as_bytes())
+            })
+            .collect()
+    }
+
+    /// The tree as JSON: each node has a `type` of `file` or `directory`, a `name` and a `size`,
+    /// which for directories is the total, and directories list their `children`.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&Node::Directory(self)).expect("trees serialize")
+    }
+
+    /// The tree drawn like the puzzle does, with the total size of each directory:
+    ///
+    /// ```text
+    /// - / (dir, size=584)
+    ///   - e (dir, size=584)
+    ///     - i (file, size=584)
+    /// ```
+    pub fn listing(&self) -> String {
+        fn draw(dir: &Directory, depth: usize, listing: &mut String) {
+            let indent = "  ".repeat(depth);
+            listing.push_str(&format!(
+                "{indent}- {} (dir, size={})\n",
+                dir.name, dir.size
+            ));
+            for child in &dir.children {
+                match child {
+                    Tree::File(f) => listing
+                        .push_str(&format!("{indent}  - {} (file, size={})\n", f.name, f.size)),
+                    Tree::Directory(d) => draw(d, depth + 1, listing),
+                }
+            }
+        }
+
+        let mut listing = String::new();
+        draw(self, 0, &mut listing);
+        listing
+    }
+
+    /// `self` and every directory below it, parents before their children.
+    pub fn descendants_and_self(&self) -> Directories<'_> {
+        Directories { stack: vec![self] }
+    }
+
+    pub fn descendant_directories(&self) -> Directories<'_> {
+        let mut directories = self.descendants_and_self();
+        directories.next();
+        directories
     }
 }
 
-pub fn parse_tree(input: &str) -> Directory {
-    let mut unparsed_lines: VecDeque<_> = input.lines().skip_while(|l| l.is_empty()).collect();
-    parse_cd(&mut unparsed_lines)
+/// An entry of a directory the shell has seen, with subdirectories as indices into the shell's
+/// directories.
+#[derive(Debug, Clone)]
+enum Seen {
+    File(File),
+    Directory(usize),
 }
 
-pub fn parse_cd(unparsed_lines: &mut VecDeque<&str>) -> Directory {
-    const PROMPT: &str = "$ cd ";
-    let parent_line = unparsed_lines.pop_front().expect("more to do");
-    assert!(parent_line.starts_with(PROMPT));
-    let name = parent_line[PROMPT.len()..].to_string();
-    let mut dir = Directory::new(name);
-    dir.children = parse_ls(unparsed_lines);
+/// A directory the shell has seen, before sizes are added up.
+#[derive(Debug, Clone)]
+struct SeenDirectory {
+    name: String,
+    parent: Option<usize>,
+    entries: Vec<Seen>,
+    /// Where each name is in `entries`
+    by_name: HashMap<String, usize>,
+}
 
-    while let Some(child_line) = unparsed_lines.front() {
-        assert!(child_line.starts_with(PROMPT));
-        let child_name = child_line[PROMPT.len()..].to_string();
-        if child_name == ".." {
-            break;
+impl SeenDirectory {
+    fn new(name: &str, parent: Option<usize>) -> Self {
+        Self {
+            name: name.to_string(),
+            parent,
+            entries: Vec::new(),
+            by_name: HashMap::new(),
         }
-        let child = dir
-            .children
-            .iter_mut()
-            .find(|c| matches!(c, Tree::Directory(d) if d.name == child_name))
-            .expect("cd into dir from ls");
-        let child_dir = parse_cd(unparsed_lines);
-        unparsed_lines.pop_front();
-        *child = Tree::Directory(child_dir);
     }
+}
 
-    dir
+/// Replays a terminal session, building the tree out of everything `ls` printed. Directories
+/// can be entered before they are listed and listed more than once.
+///
+/// Directories are kept in a flat list, each after its parent, so changing directory and adding
+/// an entry take the same time however deep the current directory is. Sizes are only added up
+/// when the tree is built.
+#[derive(Debug, Clone)]
+pub struct Shell {
+    /// Every directory seen, the root first
+    directories: Vec<SeenDirectory>,
+    /// The current directory's index in `directories`
+    cwd: usize,
+    /// Whether the last command was `ls`, so output lines are expected
+    listing: bool,
 }
 
-pub fn parse_ls(unparsed_lines: &mut VecDeque<&str>) -> Vec<Tree> {
-    assert_eq!("$ ls", unparsed_lines[0]);
-    let items: Vec<_> = unparsed_lines
-        .iter()
-        .skip(1)
-        .take_while(|l| !l.starts_with("$"))
-        .map(|l| {
-            let (dir_or_size, name) = l.split_once(' ').expect("format of ls");
-            match dir_or_size.parse() {
-                Ok(size) => Tree::File(File::new(name, size)),
-                Err(_) => Tree::Directory(Directory::new(name)),
+impl Default for Shell {
+    fn default() -> Self {
+        Self {
+            directories: vec![SeenDirectory::new("/", None)],
+            cwd: 0,
+            listing: false,
+        }
+    }
+}
+
+impl Shell {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The absolute path of the current directory.
+    pub fn cwd(&self) -> String {
+        let mut names = Vec::new();
+        let mut dir = &self.directories[self.cwd];
+        while let Some(parent) = dir.parent {
+            names.push(dir.name.as_str());
+            dir = &self.directories[parent];
+        }
+        names
+            .iter()
+            .rev()
+            .fold("/".to_string(), |path, name| join(&path, name))
+    }
+
+    /// The tree read so far.
+    pub fn tree(&self) -> Directory {
+        self.clone().into_tree()
+    }
+
+    pub fn into_tree(self) -> Directory {
+        // children come after their parents, so building from the back finds them done
+        let mut built: Vec<Option<Directory>> = vec![None; self.directories.len()];
+        for (i, seen) in self.directories.into_iter().enumerate().rev() {
+            let mut dir = Directory::new(seen.name);
+            for entry in seen.entries {
+                dir.push(match entry {
+                    Seen::File(f) => Tree::File(f),
+                    Seen::Directory(d) => {
+                        Tree::Directory(built[d].take().expect("children come after parents"))
+                    }
+                });
             }
-        })
-        .collect();
-    for _ in 0..=items.len() {
-        unparsed_lines.pop_front();
+            built[i] = Some(dir);
+        }
+        built[0].take().expect("the root is always there")
+    }
+
+    /// Runs one line of the transcript: a `$ cd` or `$ ls` command, or a line printed by `ls`.
+    /// `line` should be a slice of `input` for errors to point at it.
+    pub fn run_line(&mut self, input: &str, line: &str) -> Result<(), ParseError> {
+        let Some(command) = line.strip_prefix('$') else {
+            if !self.listing {
+                return Err(ParseError::at(input, line, "a `$` command"));
+            }
+            return self.add_entry(input, line);
+        };
+        self.listing = false;
+
+        let command = command.trim_start();
+        let (program, argument) = match command.split_once(' ') {
+            Some((program, argument)) => (program, Some(argument.trim_start())),
+            None => (command, None),
+        };
+        match (program, argument) {
+            ("cd", Some(target)) => self.cd(input, target),
+            ("cd", None) => Err(ParseError::at(
+                input,
+                &line[line.len()..],
+                "a directory to `cd` into",
+            )),
+            ("ls", None) => {
+                self.listing = true;
+                Ok(())
+            }
+            ("ls", Some(arguments)) => Err(ParseError::at(input, arguments, "`ls` on its own")),
+            _ => Err(ParseError::at(input, program, "`cd` or `ls`")),
+        }
+    }
+
+    /// Adds `entry` named `name` to the current directory, returning its index in the entries.
+    fn push_entry(&mut self, name: &str, entry: Seen) -> usize {
+        let dir = &mut self.directories[self.cwd];
+        dir.entries.push(entry);
+        dir.by_name.insert(name.to_string(), dir.entries.len() - 1);
+        dir.entries.len() - 1
+    }
+
+    fn new_directory(&mut self, name: &str) -> usize {
+        self.directories
+            .push(SeenDirectory::new(name, Some(self.cwd)));
+        let index = self.directories.len() - 1;
+        self.push_entry(name, Seen::Directory(index));
+        index
+    }
+
+    /// Changes to `target`, an absolute or relative path that may contain `..` and `.`. `cd ..`
+    /// at the root stays there, and directories not listed yet are created.
+    fn cd(&mut self, input: &str, target: &str) -> Result<(), ParseError> {
+        if target.starts_with('/') {
+            self.cwd = 0;
+        }
+        for name in target.split('/').filter(|n| !n.is_empty() && *n != ".") {
+            if name == ".." {
+                self.cwd = self.directories[self.cwd].parent.unwrap_or(0);
+                continue;
+            }
+            let dir = &self.directories[self.cwd];
+            self.cwd = match dir.by_name.get(name).map(|&i| &dir.entries[i]) {
+                Some(Seen::File(_)) => {
+                    return Err(ParseError::at(input, name, "a directory, not a file"));
+                }
+                Some(&Seen::Directory(d)) => d,
+                None => self.new_directory(name),
+            };
+        }
+        Ok(())
+    }
+
+    /// Adds a line printed by `ls` to the current directory. Listing a file again updates its
+    /// size; listing a directory again keeps what is known about its contents.
+    fn add_entry(&mut self, input: &str, line: &str) -> Result<(), ParseError> {
+        let (dir_or_size, name) = line
+            .split_once(' ')
+            .ok_or_else(|| ParseError::at(input, line, "`dir NAME` or `SIZE NAME`"))?;
+        let size = match dir_or_size.parse() {
+            Ok(size) => Some(size),
+            Err(_) if dir_or_size == "dir" => None,
+            Err(_) => return Err(ParseError::at(input, dir_or_size, "`dir` or a file size")),
+        };
+        let dir = &mut self.directories[self.cwd];
+        match (dir.by_name.get(name).map(|&i| &mut dir.entries[i]), size) {
+            (None, None) => {
+                self.new_directory(name);
+            }
+            (None, Some(size)) => {
+                self.push_entry(name, Seen::File(File::new(name, size)));
+            }
+            (Some(Seen::Directory(_)), None) => {}
+            (Some(Seen::File(existing)), Some(size)) => existing.size = size,
+            (Some(_), _) => {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    "the same kind of entry as the last listing",
+                ));
+            }
+        }
+        Ok(())
     }
-    items
 }
 
-pub fn part1(input: &str) -> usize {
-    let tree = parse_tree(input);
+/// A directory being read from a listing, with the line it was on and the size the line gave.
+struct Listed<'a> {
+    dir: Directory,
+    line: &'a str,
+    size: Option<usize>,
+}
+
+/// Checks the size of the innermost open directory and moves it into its parent, or into `root`
+/// if it is the top.
+fn close_directory(
+    input: &str,
+    open: &mut Vec<Listed>,
+    root: &mut Option<Directory>,
+) -> Result<(), ParseError> {
+    let Some(listed) = open.pop() else {
+        return Ok(());
+    };
+    if let Some(size) = listed.size.filter(|&size| size != listed.dir.size) {
+        return Err(ParseError::at(
+            input,
+            listed.line,
+            format!(
+                "a size of {}, the total of the entries below, not {size}",
+                listed.dir.size
+            ),
+        ));
+    }
+    match open.last_mut() {
+        Some(parent) => parent.dir.push(Tree::Directory(listed.dir)),
+        None => *root = Some(listed.dir),
+    }
+    Ok(())
+}
+
+/// Reads a tree drawn as by [`Directory::listing`]. Directory sizes may be left out, as in
+/// `- a (dir)`, but must be right when given.
+pub fn parse_listing(input: &str) -> Result<Directory, ParseError> {
+    let mut open: Vec<Listed> = Vec::new();
+    let mut root = None;
+    for line in parse::split_lines(input) {
+        let entry = line.trim_start_matches(' ');
+        let indent = line.len() - entry.len();
+        if indent % 2 != 0 || indent / 2 > open.len() {
+            return Err(ParseError::at(
+                input,
+                entry,
+                "two spaces more indentation than the directory above, at most",
+            ));
+        }
+        let depth = indent / 2;
+        while open.len() > depth {
+            close_directory(input, &mut open, &mut root)?;
+        }
+        if root.is_some() {
+            return Err(ParseError::at(input, line, "a single top directory"));
+        }
+
+        let description = entry
+            .strip_prefix("- ")
+            .ok_or_else(|| ParseError::at(input, entry, "`- ` and a name"))?;
+        let (name, info) = description
+            .strip_suffix(')')
+            .and_then(|d| d.rsplit_once(" ("))
+            .ok_or_else(|| {
+                ParseError::at(input, description, "NAME (dir) or NAME (file, size=N)")
+            })?;
+        if open
+            .last()
+            .is_some_and(|parent| parent.dir.child(name).is_some())
+        {
+            return Err(ParseError::at(
+                input,
+                name,
+                "a name not already in the directory",
+            ));
+        }
+        let (kind, size) = match info.split_once(", size=") {
+            Some((kind, size)) => (
+                kind,
+                Some(parse::number(size, "a size").map_err(|e| e.within(input, size))?),
+            ),
+            None => (info, None),
+        };
+        match (kind, size) {
+            ("dir", size) => open.push(Listed {
+                dir: Directory::new(name),
+                line,
+                size,
+            }),
+            ("file", Some(_)) if depth == 0 => {
+                return Err(ParseError::at(input, line, "a directory at the top"));
+            }
+            ("file", Some(size)) => open
+                .last_mut()
+                .expect("entries below the top have a directory above")
+                .dir
+                .push(Tree::File(File::new(name, size))),
+            _ => return Err(ParseError::at(input, info, "`dir` or `file, size=N`")),
+        }
+    }
+
+    while !open.is_empty() {
+        close_directory(input, &mut open, &mut root)?;
+    }
+    root.ok_or_else(|| ParseError::at(input, &input[input.len()..], "a listing"))
+}
+
+pub fn parse_tree(input: &str) -> Result<Directory, ParseError> {
+    let mut shell = Shell::new();
+    for line in parse::split_lines(input) {
+        shell.run_line(input, line)?;
+    }
+    Ok(shell.into_tree())
+}
 
+fn sum_small_directories(tree: &Directory) -> usize {
     tree.descendants_and_self()
         .map(|d| d.size())
         .filter(|&s| s <= 100000)
         .sum()
 }
 
-#[allow(dead_code, unused_variables)]
-pub fn part2(input: &str) -> usize {
-    const TOTAL_SPACE: usize = 70000000;
-    const FREE_SPACE_REQUIRED: usize = 30000000;
-    let tree = parse_tree(input);
-    let total_used = tree.size();
-    let total_unused = TOTAL_SPACE - total_used;
-    let need_to_free = FREE_SPACE_REQUIRED - total_unused;
+/// How much a disk holds and how much of it has to be left free.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Disk {
+    pub capacity: usize,
+    pub required_free: usize,
+}
 
-    tree.descendants_and_self()
-        .map(|d| d.size())
-        .filter(|&s| s >= need_to_free)
-        .min()
-        .unwrap()
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CleanupError {
+    /// The tree holds more than the disk does
+    Overfull { used: usize, capacity: usize },
+    /// Even an empty disk would not have enough free
+    TooSmall {
+        capacity: usize,
+        required_free: usize,
+    },
+}
+
+impl Display for CleanupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CleanupError::Overfull { used, capacity } => {
+                write!(f, "{used} is in use on a disk of {capacity}")
+            }
+            CleanupError::TooSmall {
+                capacity,
+                required_free,
+            } => write!(
+                f,
+                "a disk of {capacity} can never have {required_free} free"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CleanupError {}
+
+/// Directories to delete, none inside another, and how much deleting them frees.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    pub directories: Vec<(String, usize)>,
+    pub freed: usize,
+}
+
+/// Sets of totals give up past this many entries or bitset words, rather than growing
+/// without bound, and planning falls back to [`search_directories`].
+const MAX_TOTALS: usize = 1 << 21;
+
+/// A set of totals from 0 up to a limit. It is a sorted list while that is smaller than a
+/// bitset up to the limit, so large sizes only cost memory when many totals can be reached.
+#[derive(Debug, Clone)]
+enum Totals {
+    Sparse(Vec<usize>),
+    Dense(Vec<u64>),
 }
 
-pub fn main() {
-    let input = std::fs::read_to_string("input/2022/day7.txt").unwrap();
+impl Totals {
+    fn contains(&self, total: usize) -> bool {
+        match self {
+            Totals::Sparse(totals) => totals.binary_search(&total).is_ok(),
+            Totals::Dense(words) => words
+                .get(total / 64)
+                .is_some_and(|word| word & (1 << (total % 64)) != 0),
+        }
+    }
+
+    /// Every total, smallest first.
+    fn iter(&self) -> Box<dyn Iterator<Item = usize> + '_> {
+        match self {
+            Totals::Sparse(totals) => Box::new(totals.iter().copied()),
+            Totals::Dense(words) => Box::new(
+                words
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, word)| **word != 0)
+                    .flat_map(|(i, &word)| {
+                        std::iter::successors(Some(word), |w| Some(w & (w - 1)).filter(|&w| w != 0))
+                            .map(move |w| i * 64 + w.trailing_zeros() as usize)
+                    }),
+            ),
+        }
+    }
+
+    /// The smallest total of at least `min`.
+    fn first_from(&self, min: usize) -> Option<usize> {
+        self.iter().find(|&total| total >= min)
+    }
+
+    /// Switches to a bitset up to `limit`, unless that would be too big.
+    fn densify(&mut self, limit: usize) -> Option<&mut Vec<u64>> {
+        if let Totals::Sparse(totals) = self {
+            if limit / 64 >= MAX_TOTALS {
+                return None;
+            }
+            let mut words = vec![0; limit / 64 + 1];
+            for &total in totals.iter() {
+                words[total / 64] |= 1 << (total % 64);
+            }
+            *self = Totals::Dense(words);
+        }
+        match self {
+            Totals::Dense(words) => Some(words),
+            Totals::Sparse(_) => unreachable!("the totals were just made dense"),
+        }
+    }
+
+    /// Adds every total in `other` plus `shift`, dropping those over `limit`. Gives up if
+    /// there are too many totals to keep.
+    fn add_shifted(&mut self, other: &Totals, shift: usize, limit: usize) -> Option<()> {
+        if let (Totals::Sparse(totals), Totals::Sparse(others)) = (&mut *self, other) {
+            let shifted = others.iter().map(|t| t + shift).take_while(|&t| t <= limit);
+            *totals = totals.iter().copied().merge(shifted).dedup().collect();
+            if totals.len() <= MAX_TOTALS.min(limit / 64 + 1) {
+                return Some(());
+            }
+        }
+        let words = self.densify(limit)?;
+        match other {
+            Totals::Sparse(others) => {
+                for total in others.iter().map(|t| t + shift).take_while(|&t| t <= limit) {
+                    words[total / 64] |= 1 << (total % 64);
+                }
+            }
+            Totals::Dense(others) => {
+                let (word_shift, bit_shift) = (shift / 64, shift % 64);
+                for (i, &word) in others.iter().enumerate().filter(|(_, w)| **w != 0) {
+                    if let Some(target) = words.get_mut(i + word_shift) {
+                        *target |= word << bit_shift;
+                    }
+                    if bit_shift > 0
+                        && let Some(target) = words.get_mut(i + word_shift + 1)
+                    {
+                        *target |= word >> (64 - bit_shift);
+                    }
+                }
+                let last = limit % 64;
+                if let Some(word) = words.last_mut().filter(|_| last < 63) {
+                    *word &= (1 << (last + 1)) - 1;
+                }
+            }
+        }
+        Some(())
+    }
+}
+
+/// Each directory in `forest` in pre-order, with its path, its size and the position just
+/// past everything inside it.
+fn flatten(forest: &[(&Directory, String)]) -> Vec<(String, usize, usize)> {
+    fn visit(dir: &Directory, path: String, items: &mut Vec<(String, usize, usize)>) {
+        let index = items.len();
+        items.push((path.clone(), dir.size, 0));
+        for child in &dir.children {
+            if let Tree::Directory(d) = child {
+                visit(d, join(&path, &d.name), items);
+            }
+        }
+        items[index].2 = items.len();
+    }
+
+    let mut items = Vec::new();
+    for (dir, path) in forest {
+        visit(dir, path.clone(), &mut items);
+    }
+    items
+}
+
+/// The totals up to `limit` that deleting non-nested directories from `forest` can free, or
+/// `None` if there are too many to keep.
+///
+/// Directories are taken in pre-order, so deciding to delete one skips straight past
+/// everything inside it. Only the totals for the positions still to be reached are kept.
+fn cleanup_totals(forest: &[(&Directory, String)], limit: usize) -> Option<Totals> {
+    let items = flatten(forest);
+    let mut arriving: Vec<Option<Totals>> = vec![None; items.len() + 1];
+    arriving[0] = Some(Totals::Sparse(vec![0]));
+    for (i, &(_, size, past)) in items.iter().enumerate() {
+        let here = arriving[i]
+            .take()
+            .expect("every position is reached by skipping");
+        if size <= limit {
+            arriving[past]
+                .get_or_insert_with(|| Totals::Sparse(Vec::new()))
+                .add_shifted(&here, size, limit)?;
+        }
+        match &mut arriving[i + 1] {
+            Some(next) => next.add_shifted(&here, 0, limit)?,
+            next @ None => *next = Some(here),
+        }
+    }
+    arriving[items.len()].take()
+}
+
+/// Adds to `picks` some non-nested directories from `forest` that free exactly `target`, which
+/// must be possible. Splits the forest in half and finds how much each half has to free, so
+/// only a few sets of totals are held at once. The second half's totals are only needed when
+/// the first half can't free all of it.
+///
+/// Every set of totals here is a subset of the whole tree's up to a smaller limit, so this
+/// never gives up once [`cleanup_totals`] has succeeded on the whole tree.
+fn pick_directories(
+    forest: &[(&Directory, String)],
+    target: usize,
+    picks: &mut Vec<(String, usize)>,
+) {
+    match forest {
+        _ if target == 0 => {}
+        [] => unreachable!("an empty forest frees nothing"),
+        [(dir, path)] if dir.size == target => picks.push((path.clone(), target)),
+        [(dir, path)] => {
+            let children: Vec<_> = dir
+                .children
+                .iter()
+                .filter_map(|c| match c {
+                    Tree::Directory(d) => Some((d, join(path, &d.name))),
+                    Tree::File(_) => None,
+                })
+                .collect();
+            pick_directories(&children, target, picks);
+        }
+        _ => {
+            let (left, right) = forest.split_at(forest.len() / 2);
+            let left_totals = cleanup_totals(left, target).expect("fewer totals than the tree's");
+            // freeing it all from one half leaves the other alone
+            if left_totals.contains(target) {
+                return pick_directories(left, target, picks);
+            }
+            let right_totals = cleanup_totals(right, target).expect("fewer totals than the tree's");
+            let from_left = left_totals
+                .iter()
+                .find(|&t| right_totals.contains(target - t))
+                .expect("the target can be freed");
+            pick_directories(left, from_left, picks);
+            pick_directories(right, target - from_left, picks);
+        }
+    }
+}
+
+/// The non-nested directories from `forest` that free at least `need` but as little as
+/// possible, given that some directory frees `most`. For when [`cleanup_totals`] gives up.
+///
+/// Tries deleting and then keeping each directory in pre-order, dropping branches that can't
+/// free enough or can't beat the best plan so far. That only ever holds one plan, but can take
+/// time exponential in the number of directories.
+fn search_directories(forest: &[(&Directory, String)], need: usize, most: usize) -> Plan {
+    enum Step {
+        Visit { position: usize, freed: usize },
+        Unpick,
+    }
+
+    let items = flatten(forest);
+    // the most that can be freed from each position on
+    let mut reachable = vec![0; items.len() + 1];
+    for (i, &(_, size, past)) in items.iter().enumerate().rev() {
+        reachable[i] = reachable[i + 1].max(size + reachable[past]);
+    }
+    let mut best = (
+        most,
+        vec![
+            items
+                .iter()
+                .position(|&(_, size, _)| size == most)
+                .expect("some directory frees the most"),
+        ],
+    );
+    let mut picks = Vec::new();
+    let mut steps = vec![Step::Visit {
+        position: 0,
+        freed: 0,
+    }];
+    while let Some(step) = steps.pop() {
+        let (position, freed) = match step {
+            Step::Unpick => {
+                picks.pop();
+                continue;
+            }
+            Step::Visit { position, freed } => (position, freed),
+        };
+        if freed >= need {
+            if freed < best.0 {
+                best = (freed, picks.clone());
+            }
+            if best.0 == need {
+                break;
+            }
+            continue;
+        }
+        if freed + reachable[position] < need {
+            continue;
+        }
+        let (_, size, past) = items[position];
+        steps.push(Step::Visit {
+            position: position + 1,
+            freed,
+        });
+        if freed + size < best.0 {
+            picks.push(position);
+            steps.push(Step::Unpick);
+            steps.push(Step::Visit {
+                position: past,
+                freed: freed + size,
+            });
+        }
+    }
+    let (freed, picked) = best;
+    Plan {
+        directories: picked
+            .into_iter()
+            .map(|i| (items[i].0.clone(), items[i].1))
+            .collect(),
+        freed,
+    }
+}
+
+impl Disk {
+    /// The disk from the puzzle.
+    pub const PUZZLE: Disk = Disk {
+        capacity: 70000000,
+        required_free: 30000000,
+    };
+
+    /// How much of `tree` has to be deleted to leave enough free.
+    pub fn space_to_free(&self, tree: &Directory) -> Result<usize, CleanupError> {
+        if self.required_free > self.capacity {
+            return Err(CleanupError::TooSmall {
+                capacity: self.capacity,
+                required_free: self.required_free,
+            });
+        }
+        let free = self
+            .capacity
+            .checked_sub(tree.size)
+            .ok_or(CleanupError::Overfull {
+                used: tree.size,
+                capacity: self.capacity,
+            })?;
+        Ok(self.required_free.saturating_sub(free))
+    }
+
+    /// The path and size of the smallest directory that frees enough on its own, or `None`
+    /// if there is enough free already.
+    pub fn smallest_directory(
+        &self,
+        tree: &Directory,
+    ) -> Result<Option<(String, usize)>, CleanupError> {
+        let need = self.space_to_free(tree)?;
+        if need == 0 {
+            return Ok(None);
+        }
+        Ok(tree
+            .du()
+            .into_iter()
+            .filter(|&(_, size)| size >= need)
+            .min_by_key(|&(_, size)| size))
+    }
+
+    /// The directories, none inside another, that free enough while deleting as little as
+    /// possible. Nothing is deleted if there is enough free already.
+    ///
+    /// This compares every amount up to the smallest single directory that frees enough can be
+    /// freed, as long as there are at most 2^21 of them or a bitset of them fits in 2^21 words.
+    /// Past that it searches through the directories instead, which can be much slower on
+    /// trees with many large directories.
+    pub fn cheapest_directories(&self, tree: &Directory) -> Result<Plan, CleanupError> {
+        let need = self.space_to_free(tree)?;
+        let Some((_, most)) = self.smallest_directory(tree)? else {
+            return Ok(Plan {
+                directories: vec![],
+                freed: 0,
+            });
+        };
+        let forest = [(tree, "/".to_string())];
+        let Some(totals) = cleanup_totals(&forest, most) else {
+            return Ok(search_directories(&forest, need, most));
+        };
+        let freed = totals
+            .first_from(need)
+            .expect("the smallest directory frees enough");
+        let mut directories = Vec::new();
+        pick_directories(&forest, freed, &mut directories);
+        Ok(Plan { directories, freed })
+    }
+}
+
+/// The size of the smallest directory that frees enough, or 0 if nothing has to go.
+fn smallest_directory_to_free(tree: &Directory) -> Result<usize, CleanupError> {
+    Ok(Disk::PUZZLE
+        .smallest_directory(tree)?
+        .map_or(0, |(_, size)| size))
+}
+
+pub fn part1(input: &str) -> usize {
+    sum_small_directories(&parse_tree(input).unwrap())
+}
+
+pub fn part2(input: &str) -> Result<usize, CleanupError> {
+    smallest_directory_to_free(&parse_tree(input).unwrap())
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed<'a> = Directory;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse_tree(input)
+    }
+
+    fn part1(tree: &Self::Parsed<'_>) -> Answer {
+        sum_small_directories(tree).into()
+    }
+
+    fn part2(tree: &Self::Parsed<'_>) -> Answer {
+        match smallest_directory_to_free(tree) {
+            Ok(size) => size.into(),
+            Err(error) => error.to_string().into(),
+        }
+    }
+}
+
+pub fn main() -> Result<(), provider::Error> {
+    let input = provider::input(2022, 7)?;
     dbg!(part1(&input));
-    dbg!(part2(&input));
+    let _ = dbg!(part2(&input));
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples;
+    use once_cell::sync::Lazy;
 
-    const EXAMPLE: &str = r"
-$ cd /
-$ ls
-dir a
-14848514 b.txt
-8504156 c.dat
-dir d
-$ cd a
-$ ls
-dir e
-29116 f
-2557 g
-62596 h.lst
-$ cd e
-$ ls
-584 i
-$ cd ..
-$ cd ..
-$ cd d
-$ ls
-4060174 j
-8033020 d.log
-5626152 d.ext
-7214296 k";
+    static EXAMPLE: Lazy<String> = Lazy::new(|| examples::input(2022, 7, 1));
+
+    /// The total size summed from every file below, ignoring the cached totals.
+    fn recomputed_size(dir: &Directory) -> usize {
+        dir.children
+            .iter()
+            .map(|c| match c {
+                Tree::File(f) => f.size,
+                Tree::Directory(d) => recomputed_size(d),
+            })
+            .sum()
+    }
 
     #[test]
     pub fn parse_tree_example() {
-        let root = parse_tree(EXAMPLE);
+        let root = parse_tree(&EXAMPLE).unwrap();
 
         assert_eq!("/", root.name);
         assert_eq!(48381165, root.size());
+        assert_eq!(48381165, recomputed_size(&root));
         assert_children_names(&root, vec!["a", "b.txt", "c.dat", "d"]);
         assert_children_sizes(&root, vec![94853, 14848514, 8504156, 24933642]);
 
@@ -248,9 +1149,194 @@ $ ls
         );
     }
 
+    #[test]
+    pub fn messy_transcripts() {
+        let transcript = "\
+$ ls
+dir a
+10 x
+$ cd b
+$ ls
+20 y
+$ cd ..
+$ cd ..
+$ ls
+dir a
+dir b
+15 x
+$ cd /a/c
+$ ls
+5 z
+$ cd /
+$ cd a
+$ ls
+dir c
+1 w
+";
+        let root = parse_tree(transcript).unwrap();
+        let paths: Vec<String> = root
+            .walk()
+            .map(|(path, node)| format!("{path} {}", node.size()))
+            .collect();
+        assert_eq!(
+            vec![
+                "/ 41", "/a 6", "/a/c 5", "/a/c/z 5", "/a/w 1", "/x 15", "/b 20", "/b/y 20"
+            ],
+            paths
+        );
+
+        assert!(
+            root.descendants_and_self()
+                .all(|d| d.size() == recomputed_size(d))
+        );
+
+        let relisted = format!("{transcript}$ cd /a/c\n$ ls\n7 z\n");
+        let root = parse_tree(&relisted).unwrap();
+        let sizes: Vec<usize> = ["/", "/a", "/a/c"]
+            .into_iter()
+            .map(|path| root.directory(path).unwrap().size())
+            .collect();
+        assert_eq!(vec![43, 8, 7], sizes);
+
+        let mut shell = Shell::new();
+        for line in ["$ cd a/b/../c", "$ cd ./d"] {
+            shell.run_line(line, line).unwrap();
+        }
+        assert_eq!("/a/c/d", shell.cwd());
+        assert!(shell.tree().directory("/a/b").is_some());
+    }
+
+    #[test]
+    pub fn transcript_errors() {
+        let error = |transcript: &str| {
+            let e = parse_tree(transcript).unwrap_err();
+            (e.line, e.column, e.expected)
+        };
+        assert_eq!(
+            (2, 3, "`cd` or `ls`".to_string()),
+            error("$ cd /\n$ rm -rf a\n")
+        );
+        assert_eq!((1, 1, "a `$` command".to_string()), error("dir a\n$ ls\n"));
+        assert_eq!(
+            (3, 6, "a directory, not a file".to_string()),
+            error("$ ls\n10 a\n$ cd a\n")
+        );
+        assert_eq!(
+            (1, 5, "a directory to `cd` into".to_string()),
+            error("$ cd\n")
+        );
+        assert_eq!((1, 6, "`ls` on its own".to_string()), error("$ ls -l\n"));
+        assert_eq!(
+            (
+                4,
+                1,
+                "the same kind of entry as the last listing".to_string()
+            ),
+            error("$ ls\ndir a\n$ ls\n10 a\n")
+        );
+    }
+
+    /// The example tree as the puzzle draws it.
+    const LISTING: &str = "\
+- / (dir)
+  - a (dir)
+    - e (dir)
+      - i (file, size=584)
+    - f (file, size=29116)
+    - g (file, size=2557)
+    - h.lst (file, size=62596)
+  - b.txt (file, size=14848514)
+  - c.dat (file, size=8504156)
+  - d (dir)
+    - j (file, size=4060174)
+    - d.log (file, size=8033020)
+    - d.ext (file, size=5626152)
+    - k (file, size=7214296)
+";
+
+    #[test]
+    pub fn listings() {
+        let root = parse_tree(&EXAMPLE).unwrap();
+        assert_eq!(root, parse_listing(LISTING).unwrap());
+
+        let listing = root.listing();
+        let mut lines = listing.lines();
+        assert_eq!(Some("- / (dir, size=48381165)"), lines.next());
+        assert_eq!(Some("  - a (dir, size=94853)"), lines.next());
+        assert_eq!(Some("    - e (dir, size=584)"), lines.next());
+        assert_eq!(Some("      - i (file, size=584)"), lines.next());
+        assert_eq!(LISTING.lines().count(), listing.lines().count());
+        assert_eq!(root, parse_listing(&listing).unwrap());
+    }
+
+    #[test]
+    pub fn listing_errors() {
+        let error = |listing: &str| {
+            let e = parse_listing(listing).unwrap_err();
+            (e.line, e.column, e.expected)
+        };
+        let cases = [
+            (
+                "- / (dir)\n  - a (dir, size=4)\n    - b (file, size=3)\n",
+                (2, 1, "a size of 3, the total of the entries below, not 4"),
+            ),
+            (
+                "- / (dir)\n   - a (dir)\n",
+                (
+                    2,
+                    4,
+                    "two spaces more indentation than the directory above, at most",
+                ),
+            ),
+            ("- / (dir)\n- x (dir)\n", (2, 1, "a single top directory")),
+            (
+                "- / (dir)\n  - a (link)\n",
+                (2, 8, "`dir` or `file, size=N`"),
+            ),
+            ("- / (dir)\n  - a (file, size=x)\n", (2, 19, "a size")),
+            (
+                "- / (dir)\n  - a (dir)\n  - a (file, size=1)\n",
+                (3, 5, "a name not already in the directory"),
+            ),
+            ("- f (file, size=1)\n", (1, 1, "a directory at the top")),
+        ];
+        for (listing, (line, column, expected)) in cases {
+            assert_eq!(
+                (line, column, expected.to_string()),
+                error(listing),
+                "{listing:?}"
+            );
+        }
+    }
+
+    #[test]
+    pub fn json() {
+        let root =
+            parse_listing("- / (dir)\n  - a (dir)\n    - b (file, size=3)\n  - c (file, size=4)\n")
+                .unwrap();
+        let json: serde_json::Value = serde_json::from_str(&root.to_json()).unwrap();
+        assert_eq!(
+            serde_json::json!({
+                "type": "directory",
+                "name": "/",
+                "size": 7,
+                "children": [
+                    {
+                        "type": "directory",
+                        "name": "a",
+                        "size": 3,
+                        "children": [{ "type": "file", "name": "b", "size": 3 }]
+                    },
+                    { "type": "file", "name": "c", "size": 4 }
+                ]
+            }),
+            json
+        );
+    }
+
     #[test]
     pub fn part1_example() {
-        assert_eq!(95437, part1(EXAMPLE));
+        assert_eq!(95437, part1(&EXAMPLE));
     }
 
     #[test]
@@ -275,8 +1361,250 @@ $ ls
         );
     }
 
+    #[test]
+    pub fn paths() {
+        let root = parse_tree(&EXAMPLE).unwrap();
+
+        assert_eq!(584, root.directory("/a/e").unwrap().size());
+        assert_eq!(7214296, root.file("/d/k").unwrap().size());
+        assert_eq!(Some(Node::Directory(&root)), root.get("/"));
+        assert_eq!(None, root.file("/a"));
+        assert_eq!(None, root.get("/a/x"));
+        assert_eq!(None, root.get("/b.txt/x"));
+        assert_eq!(None, root.get("a/e"));
+        assert_eq!("a", root.parent("/a/e").unwrap().name());
+        assert_eq!("/", root.parent("/d").unwrap().name());
+        assert_eq!(None, root.parent("/"));
+        assert_eq!(None, root.parent("/x"));
+
+        let paths: Vec<String> = root.walk().map(|(path, _)| path).collect();
+        assert_eq!(
+            vec![
+                "/", "/a", "/a/e", "/a/e/i", "/a/f", "/a/g", "/a/h.lst", "/b.txt", "/c.dat", "/d",
+                "/d/j", "/d/d.log", "/d/d.ext", "/d/k"
+            ],
+            paths
+        );
+    }
+
+    #[test]
+    pub fn queries() {
+        let root = parse_tree(&EXAMPLE).unwrap();
+        let sizes = |list: Vec<(String, usize)>| {
+            list.into_iter()
+                .map(|(path, size)| format!("{size} {path}"))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            vec!["584 /a/e", "94853 /a", "24933642 /d", "48381165 /"],
+            sizes(root.du())
+        );
+        assert_eq!(
+            vec!["48381165 /", "24933642 /d"],
+            sizes(root.largest_directories(2))
+        );
+
+        let found = |pattern| {
+            root.find_files(pattern)
+                .into_iter()
+                .map(|(path, _)| path)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(vec!["/d/d.log", "/d/d.ext"], found("d.*"));
+        assert_eq!(
+            vec!["/a/h.lst", "/b.txt", "/c.dat", "/d/d.ext"],
+            found("?.*t")
+        );
+        assert_eq!(vec!["/a/f", "/a/g", "/a/h.lst"], found("/a/*"));
+        assert_eq!(vec!["/a/e/i", "/a/f", "/a/g", "/a/h.lst"], found("/a/**"));
+        assert_eq!(Vec::<String>::new(), found("*.exe"));
+    }
+
+    #[test]
+    pub fn cleanup() {
+        let root = parse_tree(&EXAMPLE).unwrap();
+        assert_eq!(Ok(8381165), Disk::PUZZLE.space_to_free(&root));
+        assert_eq!(
+            Ok(Some(("/d".to_string(), 24933642))),
+            Disk::PUZZLE.smallest_directory(&root)
+        );
+        assert_eq!(
+            Ok(Plan {
+                directories: vec![("/d".to_string(), 24933642)],
+                freed: 24933642
+            }),
+            Disk::PUZZLE.cheapest_directories(&root)
+        );
+
+        let root = parse_listing(
+            "\
+- / (dir)
+  - x (dir)
+    - x1 (dir)
+      - a (file, size=4)
+    - x2 (dir)
+      - b (file, size=2)
+  - y (dir)
+    - c (file, size=5)
+  - z (dir)
+    - d (file, size=12)
+",
+        )
+        .unwrap();
+        let disk = Disk {
+            capacity: 30,
+            required_free: 16,
+        };
+        assert_eq!(Ok(9), disk.space_to_free(&root));
+        assert_eq!(
+            Ok(Some(("/z".to_string(), 12))),
+            disk.smallest_directory(&root)
+        );
+        assert_eq!(
+            Ok(Plan {
+                directories: vec![("/x/x1".to_string(), 4), ("/y".to_string(), 5)],
+                freed: 9
+            }),
+            disk.cheapest_directories(&root)
+        );
+        assert_eq!(
+            Plan {
+                directories: vec![("/x/x1".to_string(), 4), ("/y".to_string(), 5)],
+                freed: 9
+            },
+            search_directories(&[(&root, "/".to_string())], 9, 12)
+        );
+
+        let roomy = Disk {
+            capacity: 40,
+            required_free: 10,
+        };
+        assert_eq!(
+            Ok(Plan {
+                directories: vec![],
+                freed: 0
+            }),
+            roomy.cheapest_directories(&root)
+        );
+        assert_eq!(Ok(None), roomy.smallest_directory(&root));
+
+        let tiny = Disk {
+            capacity: 10,
+            required_free: 20,
+        };
+        assert_eq!(
+            Err(CleanupError::TooSmall {
+                capacity: 10,
+                required_free: 20
+            }),
+            tiny.cheapest_directories(&root)
+        );
+        let full = Disk {
+            capacity: 20,
+            required_free: 5,
+        };
+        assert_eq!(
+            Err(CleanupError::Overfull {
+                used: 23,
+                capacity: 20
+            }),
+            full.smallest_directory(&root)
+        );
+    }
+
+    #[test]
+    pub fn cleanup_edge_cases() {
+        let empty = Directory::new("/");
+        assert_eq!(Ok(None), Disk::PUZZLE.smallest_directory(&empty));
+        assert_eq!(
+            Ok(Plan {
+                directories: vec![],
+                freed: 0
+            }),
+            Disk::PUZZLE.cheapest_directories(&empty)
+        );
+        assert_eq!(Ok(0), smallest_directory_to_free(&empty));
+
+        // sizes far beyond anything a bitset of bytes could hold
+        let root = parse_listing(
+            "\
+- / (dir)
+  - a (dir)
+    - f (file, size=400000000000)
+  - b (dir)
+    - g (file, size=500000000000)
+",
+        )
+        .unwrap();
+        let disk = Disk {
+            capacity: 1000000000000,
+            required_free: 300000000000,
+        };
+        assert_eq!(
+            Ok(Some(("/a".to_string(), 400000000000))),
+            disk.smallest_directory(&root)
+        );
+        assert_eq!(
+            Ok(Plan {
+                directories: vec![("/a".to_string(), 400000000000)],
+                freed: 400000000000
+            }),
+            disk.cheapest_directories(&root)
+        );
+
+        // every subset of these frees a different amount
+        let mut root = Directory::new("/");
+        for k in 0..22 {
+            let mut dir = Directory::new(format!("d{k}"));
+            dir.push(Tree::File(File::new("f", 1000000000 << k)));
+            root.push(Tree::Directory(dir));
+        }
+        let disk = Disk {
+            capacity: root.size(),
+            required_free: (1000000000 << 21) - 1,
+        };
+        assert_eq!(
+            Ok(Plan {
+                directories: vec![("/d21".to_string(), 1000000000 << 21)],
+                freed: 1000000000 << 21
+            }),
+            disk.cheapest_directories(&root)
+        );
+        let disk = Disk {
+            capacity: root.size(),
+            required_free: 1000000000 * ((1 << 21) - 1),
+        };
+        assert_eq!(
+            Ok(Plan {
+                directories: (0..21)
+                    .map(|k| (format!("/d{k}"), 1000000000 << k))
+                    .collect(),
+                freed: 1000000000 * ((1 << 21) - 1)
+            }),
+            disk.cheapest_directories(&root)
+        );
+    }
+
     #[test]
     pub fn part2_example() {
-        assert_eq!(24933642, part2(EXAMPLE));
+        assert_eq!(Ok(24933642), part2(&EXAMPLE));
+    }
+
+    #[test]
+    pub fn part2_overfull() {
+        let input = "$ cd /\n$ ls\n80000000 big\n";
+        assert_eq!(
+            Err(CleanupError::Overfull {
+                used: 80000000,
+                capacity: 70000000
+            }),
+            part2(input)
+        );
+        let tree = Day::parse(input).unwrap();
+        assert_eq!(
+            Answer::from("80000000 is in use on a disk of 70000000"),
+            Day::part2(&tree)
+        );
     }
 }
@@ -1,83 +1,53 @@
+use crate::{
+    provider,
+    solution::{Answer, Solution},
+    util::{Grid, ParseError, parse},
+};
+
 type Tree = (u8, bool);
-type Grid = Vec<Vec<Tree>>;
-
-fn parse_unmarked_grid(input: &str) -> Grid {
-    input
-        .trim()
-        .lines()
-        .map(|l| {
-            l.chars()
-                .map(|c| {
-                    c.to_digit(10)
-                        .expect("input is digits")
-                        .try_into()
-                        .expect("max height is 9")
-                })
-                .map(|d| (d, false))
-                .collect()
-        })
-        .collect()
+
+fn parse_unmarked_grid(input: &str) -> Result<Grid<Tree>, ParseError> {
+    parse::grid(input, "a tree height digit", |c| {
+        c.to_digit(10).map(|d| (d as u8, false))
+    })
 }
 
-fn mark_grid_visibility(grid: &mut Grid) {
-    let height = grid.len();
-    let width = grid[0].len();
-
-    for i in 0..height {
-        assert_eq!(width, grid[i].len());
-        for j in 0..width {
-            // visible from left
-            if grid[i][..j].iter().all(|t| t.0 < grid[i][j].0) {
-                grid[i][j].1 = true;
-            }
-            // visible from top
-            if grid[..i].iter().map(|r| r[j]).all(|t| t.0 < grid[i][j].0) {
-                grid[i][j].1 = true;
-            }
-            // visible from right
-            if grid[i][j..].iter().skip(1).all(|t| t.0 < grid[i][j].0) {
-                grid[i][j].1 = true;
-            }
-            // visible from bottom
-            if grid[i..]
-                .iter()
-                .skip(1)
-                .map(|r| r[j])
-                .all(|t| t.0 < grid[i][j].0)
-            {
-                grid[i][j].1 = true;
-            }
-        }
+fn mark_grid_visibility(grid: &mut Grid<Tree>) {
+    for (x, y) in grid.positions() {
+        let height = grid[(x, y)].0;
+        let row = grid.row(y);
+        let visible = row[..x].iter().all(|t| t.0 < height)
+            || row[x + 1..].iter().all(|t| t.0 < height)
+            || grid.column(x).take(y).all(|t| t.0 < height)
+            || grid.column(x).skip(y + 1).all(|t| t.0 < height);
+        grid[(x, y)].1 = visible;
     }
 }
 
-pub fn parse_grid(input: &str) -> Grid {
-    let mut grid = parse_unmarked_grid(input);
+pub fn parse_grid(input: &str) -> Result<Grid<Tree>, ParseError> {
+    let mut grid = parse_unmarked_grid(input)?;
     mark_grid_visibility(&mut grid);
-    grid
+    Ok(grid)
 }
 
-pub fn part1(input: &str) -> usize {
-    let grid = parse_grid(input);
-    grid.iter().flat_map(|r| r.iter().filter(|t| t.1)).count()
+fn count_visible(grid: &Grid<Tree>) -> usize {
+    grid.iter().filter(|t| t.1).count()
 }
 
-#[allow(dead_code, unused_variables)]
-pub fn part2(input: &str) -> usize {
-    let grid = parse_unmarked_grid(input);
-    (0..grid.len())
-        .flat_map(|i| (0..grid.len()).map(move |j| (i, j)))
-        .map(|(i, j)| scenic_score(&grid, i, j))
+fn best_scenic_score(grid: &Grid<Tree>) -> usize {
+    grid.positions()
+        .map(|(x, y)| scenic_score(grid, y, x))
         .max()
         .unwrap()
 }
 
-fn scenic_score(grid: &Grid, i: usize, j: usize) -> usize {
-    let height = grid[i][j].0;
-    let to_left = count_trees(height, grid[i][..j].iter().rev().copied());
-    let to_up = count_trees(height, grid[..i].iter().rev().map(|r| r[j]));
-    let to_right = count_trees(height, grid[i].iter().skip(j + 1).copied());
-    let to_down = count_trees(height, grid.iter().skip(i + 1).map(|r| r[j]));
+fn scenic_score(grid: &Grid<Tree>, row: usize, col: usize) -> usize {
+    let height = grid[(col, row)].0;
+    let trees = grid.row(row);
+    let to_left = count_trees(height, trees[..col].iter().rev().copied());
+    let to_right = count_trees(height, trees[col + 1..].iter().copied());
+    let to_up = count_trees(height, grid.column(col).take(row).rev().copied());
+    let to_down = count_trees(height, grid.column(col).skip(row + 1).copied());
 
     to_up * to_left * to_down * to_right
 }
@@ -95,26 +65,50 @@ fn count_trees(height: u8, mut trees: impl Iterator<Item = Tree>) -> usize {
     }
 }
 
-pub fn main() {
-    let input = std::fs::read_to_string("input/2022/day8.txt").unwrap();
+pub fn part1(input: &str) -> usize {
+    count_visible(&parse_grid(input).unwrap())
+}
+
+pub fn part2(input: &str) -> usize {
+    best_scenic_score(&parse_unmarked_grid(input).unwrap())
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed<'a> = Grid<Tree>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse_grid(input)
+    }
+
+    fn part1(grid: &Self::Parsed<'_>) -> Answer {
+        count_visible(grid).into()
+    }
+
+    fn part2(grid: &Self::Parsed<'_>) -> Answer {
+        best_scenic_score(grid).into()
+    }
+}
+
+pub fn main() -> Result<(), provider::Error> {
+    let input = provider::input(2022, 8)?;
     dbg!(part1(&input));
     dbg!(part2(&input));
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples;
+    use once_cell::sync::Lazy;
 
-    const EXAMPLE: &str = r"
-30373
-25512
-65332
-33549
-35390";
+    static EXAMPLE: Lazy<String> = Lazy::new(|| examples::input(2022, 8, 1));
 
     #[test]
     pub fn part1_example() {
-        assert_eq!(21, part1(EXAMPLE));
+        assert_eq!(21, part1(&EXAMPLE));
     }
 
     #[test]
@@ -127,14 +121,14 @@ mod tests {
 
     #[test]
     pub fn test_scenic_score() {
-        let grid = parse_grid(EXAMPLE);
+        let grid = parse_grid(&EXAMPLE).unwrap();
         assert_eq!(4, scenic_score(&grid, 1, 2));
         assert_eq!(8, scenic_score(&grid, 3, 2));
     }
 
     #[test]
     pub fn part2_example() {
-        assert_eq!(8, part2(EXAMPLE));
+        assert_eq!(8, part2(&EXAMPLE));
     }
 
     #[test]
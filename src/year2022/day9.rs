@@ -1,6 +1,8 @@
-use std::str::FromStr;
-
-type Point = (isize, isize);
+use crate::{
+    provider,
+    solution::{Answer, Solution},
+    util::{Direction, ParseError, Point, parse},
+};
 
 struct Simulation<const SNAKE_LEN: usize> {
     snake: [Point; SNAKE_LEN],
@@ -18,28 +20,6 @@ impl<const L: usize> Default for Simulation<L> {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl FromStr for Direction {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "U" => Ok(Direction::Up),
-            "D" => Ok(Direction::Down),
-            "L" => Ok(Direction::Left),
-            "R" => Ok(Direction::Right),
-            _ => Err(()),
-        }
-    }
-}
-
 impl<const L: usize> Simulation<L> {
     pub fn step_all(&mut self, steps: &[(Direction, usize)]) {
         for (dir, count) in steps {
@@ -50,24 +30,12 @@ impl<const L: usize> Simulation<L> {
     }
 
     pub fn step_once(&mut self, direction: Direction) {
-        match direction {
-            Direction::Up => self.snake[0].1 += 1,
-            Direction::Down => self.snake[0].1 -= 1,
-            Direction::Left => self.snake[0].0 -= 1,
-            Direction::Right => self.snake[0].0 += 1,
-        }
+        self.snake[0] += direction.offset();
 
         for i in 1..L {
-            let offset = (
-                self.snake[i - 1].0 - self.snake[i].0,
-                self.snake[i - 1].1 - self.snake[i].1,
-            );
-            match offset {
-                (x, y) if x.abs() <= 1 && y.abs() <= 1 => (),
-                (x, y) => {
-                    self.snake[i].0 += x.signum();
-                    self.snake[i].1 += y.signum();
-                }
+            let (leader, follower) = (self.snake[i - 1], self.snake[i]);
+            if leader.chebyshev(follower) > 1 {
+                self.snake[i] += (leader - follower).signum();
             }
         }
 
@@ -79,30 +47,27 @@ impl<const L: usize> Simulation<L> {
     }
 }
 
-fn run_simulation<const L: usize>(input: &str) -> Simulation<L> {
-    let steps = parse_steps(input);
+fn run_simulation<const L: usize>(steps: &[(Direction, usize)]) -> Simulation<L> {
     let mut simulation: Simulation<L> = Default::default();
-    simulation.step_all(&steps);
+    simulation.step_all(steps);
 
     simulation
 }
 
-fn parse_steps(input: &str) -> Vec<(Direction, usize)> {
-    input
-        .lines()
-        .filter(|l| !l.is_empty())
-        .map(|l| l.split_ascii_whitespace())
-        .map(|mut iter| {
-            (
-                iter.next().unwrap().parse().unwrap(),
-                iter.next().unwrap().parse().unwrap(),
-            )
-        })
-        .collect()
+pub fn parse_steps(input: &str) -> Result<Vec<(Direction, usize)>, ParseError> {
+    parse::lines(input, |l| {
+        let (direction, count) = parse::split_once(l, " ", "a direction and a step count")?;
+        Ok((
+            direction
+                .parse()
+                .map_err(|e: ParseError| e.within(l, direction))?,
+            parse::number(count, "a step count").map_err(|e| e.within(l, count))?,
+        ))
+    })
 }
 
-fn distinct_tail_visits<const L: usize>(input: &str) -> usize {
-    let simulation = run_simulation::<L>(input);
+fn distinct_tail_visits<const L: usize>(steps: &[(Direction, usize)]) -> usize {
+    let simulation = run_simulation::<L>(steps);
     // dbg!(&simulation.tail_history);
     let mut sorted = simulation.tail_history.clone();
     sorted.sort();
@@ -111,52 +76,57 @@ fn distinct_tail_visits<const L: usize>(input: &str) -> usize {
 }
 
 pub fn part1(input: &str) -> usize {
-    distinct_tail_visits::<2>(input)
+    distinct_tail_visits::<2>(&parse_steps(input).unwrap())
 }
 
 #[allow(dead_code, unused_variables)]
 pub fn part2(input: &str) -> usize {
-    distinct_tail_visits::<10>(input)
+    distinct_tail_visits::<10>(&parse_steps(input).unwrap())
 }
 
-pub fn main() {
-    let input = std::fs::read_to_string("input/2022/day9.txt").unwrap();
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed<'a> = Vec<(Direction, usize)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse_steps(input)
+    }
+
+    fn part1(steps: &Self::Parsed<'_>) -> Answer {
+        distinct_tail_visits::<2>(steps).into()
+    }
+
+    fn part2(steps: &Self::Parsed<'_>) -> Answer {
+        distinct_tail_visits::<10>(steps).into()
+    }
+}
+
+pub fn main() -> Result<(), provider::Error> {
+    let input = provider::input(2022, 9)?;
     dbg!(part1(&input));
     dbg!(part2(&input));
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples;
+    use once_cell::sync::Lazy;
+
+    static EXAMPLE1: Lazy<String> = Lazy::new(|| examples::input(2022, 9, 1));
 
-    const EXAMPLE1: &str = r"
-R 4
-U 4
-L 3
-D 1
-R 4
-D 1
-L 5
-R 2";
-
-    const EXAMPLE2: &str = r"
-R 5
-U 8
-L 8
-D 3
-R 17
-D 10
-L 25
-U 20";
+    static EXAMPLE2: Lazy<String> = Lazy::new(|| examples::input(2022, 9, 2));
 
     #[test]
     pub fn part1_example() {
-        assert_eq!(13, part1(EXAMPLE1));
+        assert_eq!(13, part1(&EXAMPLE1));
     }
 
     #[test]
     pub fn part2_example() {
-        assert_eq!(1, part2(EXAMPLE1));
-        assert_eq!(36, part2(EXAMPLE2));
+        assert_eq!(1, part2(&EXAMPLE1));
+        assert_eq!(36, part2(&EXAMPLE2));
     }
 }
@@ -1,12 +1,44 @@
-pub fn main() {
-    let input = std::fs::read_to_string("input/2025/day1.txt").unwrap();
+use crate::{
+    provider,
+    solution::{Answer, Solution},
+    util::{ParseError, parse},
+};
+
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed<'a> = Vec<isize>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse_turns(input)
+    }
+
+    fn part1(turns: &Self::Parsed<'_>) -> Answer {
+        count_zeros(turns.iter().copied()).into()
+    }
+
+    fn part2(turns: &Self::Parsed<'_>) -> Answer {
+        count_zero_transitions(turns.iter().copied()).into()
+    }
+}
+
+pub fn main() -> Result<(), provider::Error> {
+    let input = provider::input(2025, 1)?;
     dbg!(part1(&input));
     dbg!(part2(&input));
+    Ok(())
 }
 
 pub fn part1(input: &str) -> usize {
-    let turns = parse_turns(input);
+    count_zeros(parse_turns(input).unwrap().into_iter())
+}
+
+#[allow(dead_code, unused_variables)]
+pub fn part2(input: &str) -> isize {
+    count_zero_transitions(parse_turns(input).unwrap().into_iter())
+}
 
+fn count_zeros(turns: impl Iterator<Item = isize>) -> usize {
     let mut pos = 50;
     let mut n_zeros = 0;
     for turn in turns {
@@ -21,10 +53,7 @@ pub fn part1(input: &str) -> usize {
     n_zeros
 }
 
-#[allow(dead_code, unused_variables)]
-pub fn part2(input: &str) -> isize {
-    let turns = parse_turns(input);
-
+fn count_zero_transitions(turns: impl Iterator<Item = isize>) -> isize {
     let mut pos = 50;
     let mut n_zero_transitions = 0;
     for turn in turns {
@@ -50,40 +79,37 @@ pub fn part2(input: &str) -> isize {
     n_zero_transitions
 }
 
-fn parse_turns(input: &str) -> impl Iterator<Item = isize> + use<'_> {
-    input.split_ascii_whitespace().map(|s| {
-        let (d, amt) = s.split_at(1);
-        match d {
-            "L" => -amt.parse::<isize>().unwrap(),
-            "R" => amt.parse().unwrap(),
-            _ => panic!("unexpected prefix {d}"),
-        }
-    })
+fn parse_turns(input: &str) -> Result<Vec<isize>, ParseError> {
+    input
+        .split_ascii_whitespace()
+        .map(|s| {
+            let sign = match s.as_bytes()[0] {
+                b'L' => -1,
+                b'R' => 1,
+                _ => return Err(ParseError::at(input, s, "a turn like L68 or R48")),
+            };
+            let amt = &s[1..];
+            let amt: isize = parse::number(amt, "a distance").map_err(|e| e.within(input, amt))?;
+            Ok(sign * amt)
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples;
+    use once_cell::sync::Lazy;
 
-    const EXAMPLE: &str = r"L68
-L30
-R48
-L5
-R60
-L55
-L1
-L99
-R14
-L82
-";
+    static EXAMPLE: Lazy<String> = Lazy::new(|| examples::input(2025, 1, 1));
 
     #[test]
     pub fn part1_example() {
-        assert_eq!(3, part1(EXAMPLE));
+        assert_eq!(3, part1(&EXAMPLE));
     }
 
     #[test]
     pub fn part2_example() {
-        assert_eq!(6, part2(EXAMPLE));
+        assert_eq!(6, part2(&EXAMPLE));
     }
 }
@@ -1,61 +1,119 @@
-pub fn main() {
-    let input = std::fs::read_to_string("input/2025/day2.txt").unwrap();
+use std::ops::RangeInclusive;
+
+use crate::{
+    provider,
+    solution::{Answer, Solution},
+    util::{ParseError, scan},
+};
+
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed<'a> = Vec<RangeInclusive<usize>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(ranges: &Self::Parsed<'_>) -> Answer {
+        sum_invalid(ranges, is_doubled).into()
+    }
+
+    fn part2(ranges: &Self::Parsed<'_>) -> Answer {
+        sum_invalid(ranges, is_repeated).into()
+    }
+}
+
+pub fn main() -> Result<(), provider::Error> {
+    let input = provider::input(2025, 2)?;
     dbg!(part1(&input));
     dbg!(part2(&input));
+    Ok(())
 }
 
 pub fn part1(input: &str) -> usize {
-    parse(input)
-        .filter(|n| {
-            let s = format!("{n}");
-            s.len() % 2 == 0 && {
-                let (l, r) = s.split_at(s.len() / 2);
-                l == r
-            }
-        })
-        .sum()
+    sum_invalid(&parse(input).unwrap(), is_doubled)
 }
 
 #[allow(dead_code, unused_variables)]
 pub fn part2(input: &str) -> usize {
-    parse(input)
-        .filter(|n| {
-            let s = format!("{n}");
-            let s = s.as_bytes();
-            'outer: for l in (1..=s.len() / 2).filter(|i| s.len() % i == 0) {
-                for p in 0..s.len() {
-                    if s[p % l] != s[p] {
-                        continue 'outer;
-                    }
-                }
-                return true;
-            }
-            false
-        })
+    sum_invalid(&parse(input).unwrap(), is_repeated)
+}
+
+fn sum_invalid(ranges: &[RangeInclusive<usize>], invalid: fn(usize) -> bool) -> usize {
+    ranges
+        .iter()
+        .cloned()
+        .flatten()
+        .filter(|&n| invalid(n))
         .sum()
 }
 
-fn parse(input: &str) -> impl Iterator<Item = usize> + use<'_> {
+fn is_doubled(n: usize) -> bool {
+    let s = format!("{n}");
+    s.len() % 2 == 0 && {
+        let (l, r) = s.split_at(s.len() / 2);
+        l == r
+    }
+}
+
+fn is_repeated(n: usize) -> bool {
+    let s = format!("{n}");
+    let s = s.as_bytes();
+    'outer: for l in (1..=s.len() / 2).filter(|i| s.len() % i == 0) {
+        for p in 0..s.len() {
+            if s[p % l] != s[p] {
+                continue 'outer;
+            }
+        }
+        return true;
+    }
+    false
+}
+
+fn parse(input: &str) -> Result<Vec<RangeInclusive<usize>>, ParseError> {
     input
         .trim()
-        .split(",")
-        .map(|r| r.split_once("-").unwrap())
-        .flat_map(|(l, r)| l.parse().unwrap()..=r.parse().unwrap())
+        .split(',')
+        .map(|r| match scan::fields(r, &["", "-", ""]) {
+            Some([l, h]) => Ok(l..=h),
+            None => Err(ParseError::at(input, r, "a range like 11-22")),
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples;
+    use once_cell::sync::Lazy;
 
-    const EXAMPLE: &str = r"11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
+    static EXAMPLE: Lazy<String> = Lazy::new(|| examples::input(2025, 2, 1));
 
     #[test]
     pub fn part1_example() {
-        assert_eq!(1227775554, part1(EXAMPLE));
+        assert_eq!(1227775554, part1(&EXAMPLE));
     }
 
     #[test]
     pub fn part2_example() {
-        assert_eq!(4174379265, part2(EXAMPLE));
+        assert_eq!(4174379265, part2(&EXAMPLE));
+    }
+
+    #[test]
+    pub fn malformed_ranges() {
+        assert_eq!(Ok(vec![11..=22, 95..=115]), parse("11-22,95-115\n"));
+        for (input, column, text) in [
+            ("1x-22", 1, "1x-22"),
+            ("11-22-99", 1, "11-22-99"),
+            ("11-22,95-", 7, "95-"),
+            ("1-99999999999999999999", 1, "1-99999999999999999999"),
+        ] {
+            let error = parse(input).unwrap_err();
+            assert_eq!(
+                (1, column, text),
+                (error.line, error.column, error.text.as_str())
+            );
+        }
     }
 }
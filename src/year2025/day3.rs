@@ -1,22 +1,59 @@
-pub fn main() {
-    let input = std::fs::read_to_string("input/2025/day3.txt").unwrap();
+use crate::{
+    provider,
+    solution::{Answer, Solution},
+    util::ParseError,
+};
+
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed<'a> = Vec<&'a str>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse_banks(input)
+    }
+
+    fn part1(banks: &Self::Parsed<'_>) -> Answer {
+        total_joltage(banks, 2).into()
+    }
+
+    fn part2(banks: &Self::Parsed<'_>) -> Answer {
+        total_joltage(banks, 12).into()
+    }
+}
+
+pub fn main() -> Result<(), provider::Error> {
+    let input = provider::input(2025, 3)?;
     dbg!(part1(&input));
     dbg!(part2(&input));
+    Ok(())
 }
 
 pub fn part1(input: &str) -> usize {
-    input
-        .split_ascii_whitespace()
-        .map(|bank| largest(bank, 2))
-        .sum()
+    total_joltage(&parse_banks(input).unwrap(), 2)
 }
 
 #[allow(dead_code, unused_variables)]
 pub fn part2(input: &str) -> usize {
+    total_joltage(&parse_banks(input).unwrap(), 12)
+}
+
+/// Banks of battery joltage digits, one per word.
+fn parse_banks(input: &str) -> Result<Vec<&str>, ParseError> {
     input
         .split_ascii_whitespace()
-        .map(|bank| largest(bank, 12))
-        .sum()
+        .map(|bank| match bank.find(|c: char| !c.is_ascii_digit()) {
+            Some(i) => {
+                let end = i + bank[i..].chars().next().unwrap().len_utf8();
+                Err(ParseError::at(input, &bank[i..end], "a joltage digit"))
+            }
+            None => Ok(bank),
+        })
+        .collect()
+}
+
+fn total_joltage(banks: &[&str], len: usize) -> usize {
+    banks.iter().map(|bank| largest(bank, len)).sum()
 }
 
 fn largest(bank: &str, len: usize) -> usize {
@@ -31,25 +68,24 @@ fn largest(bank: &str, len: usize) -> usize {
             }
         }
     }
-    String::from_utf8(max).unwrap().parse::<usize>().unwrap()
+    max.iter().fold(0, |n, d| n * 10 + (d - b'0') as usize)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples;
+    use once_cell::sync::Lazy;
 
-    const EXAMPLE: &str = r"987654321111111
-811111111111119
-234234234234278
-818181911112111";
+    static EXAMPLE: Lazy<String> = Lazy::new(|| examples::input(2025, 3, 1));
 
     #[test]
     pub fn part1_example() {
-        assert_eq!(357, part1(EXAMPLE));
+        assert_eq!(357, part1(&EXAMPLE));
     }
 
     #[test]
     pub fn part2_example() {
-        assert_eq!(3121910778619, part2(EXAMPLE));
+        assert_eq!(3121910778619, part2(&EXAMPLE));
     }
 }
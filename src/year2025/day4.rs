@@ -1,30 +1,63 @@
-use std::{
-    ops::{Index, IndexMut},
-    str::FromStr,
+use crate::{
+    provider,
+    solution::{Answer, Solution},
+    util::{Grid, ParseError, parse},
 };
 
-pub fn main() {
-    let input = std::fs::read_to_string("input/2025/day4.txt").unwrap();
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed<'a> = Grid<bool>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse_grid(input)
+    }
+
+    fn part1(grid: &Self::Parsed<'_>) -> Answer {
+        count_accessible(grid).into()
+    }
+
+    fn part2(grid: &Self::Parsed<'_>) -> Answer {
+        remove_all_accessible(grid.clone()).into()
+    }
+}
+
+pub fn main() -> Result<(), provider::Error> {
+    let input = provider::input(2025, 4)?;
     dbg!(part1(&input));
     dbg!(part2(&input));
+    Ok(())
 }
 
 pub fn part1(input: &str) -> usize {
-    let grid: Grid = input.parse().unwrap();
-
-    grid.indices()
-        .filter(|idx| is_accessible(&grid, *idx))
-        .count()
+    count_accessible(&parse_grid(input).unwrap())
 }
 
 #[allow(dead_code, unused_variables)]
 pub fn part2(input: &str) -> usize {
-    let mut grid: Grid = input.parse().unwrap();
+    remove_all_accessible(parse_grid(input).unwrap())
+}
+
+fn parse_grid(input: &str) -> Result<Grid<bool>, ParseError> {
+    parse::grid(input, "`@` or `.`", |c| match c {
+        '@' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
+}
+
+fn count_accessible(grid: &Grid<bool>) -> usize {
+    grid.positions()
+        .filter(|idx| is_accessible(grid, *idx))
+        .count()
+}
+
+fn remove_all_accessible(mut grid: Grid<bool>) -> usize {
     let mut removed = 0;
 
     loop {
         let remove: Vec<_> = grid
-            .indices()
+            .positions()
             .filter(|idx| is_accessible(&grid, *idx))
             .collect();
 
@@ -38,91 +71,41 @@ pub fn part2(input: &str) -> usize {
     }
 }
 
-fn is_accessible(grid: &Grid, idx: (usize, usize)) -> bool {
-    grid[idx] && grid.neighbors(idx.0, idx.1).filter(|b| *b).count() < 4
-}
-
-struct Grid {
-    cells: Vec<bool>,
-    width: usize,
-}
-
-impl Index<(usize, usize)> for Grid {
-    type Output = bool;
-
-    fn index(&self, index: (usize, usize)) -> &Self::Output {
-        &self.cells[index.1 * self.width + index.0]
-    }
-}
-
-impl IndexMut<(usize, usize)> for Grid {
-    fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
-        &mut self.cells[index.1 * self.width + index.0]
-    }
-}
-
-impl FromStr for Grid {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut lines = s.split_ascii_whitespace().peekable();
-        let width = lines.peek().ok_or(())?.len();
-        let cells = lines.flat_map(|l| l.chars()).map(|c| c == '@').collect();
-        Ok(Self { cells, width })
-    }
-}
-
-const NEIGHBOR_OFFSETS: [(isize, isize); 8] = [
-    (-1, -1),
-    (-1, 0),
-    (-1, 1),
-    (0, -1),
-    (0, 1),
-    (1, -1),
-    (1, 0),
-    (1, 1),
-];
-
-impl Grid {
-    pub fn indices(&self) -> impl Iterator<Item = (usize, usize)> {
-        (0..self.width).flat_map(|i| std::iter::repeat(i).zip(0..self.width))
-    }
-
-    pub fn neighbors(&self, i: usize, j: usize) -> impl Iterator<Item = bool> {
-        NEIGHBOR_OFFSETS.iter().filter_map(move |(o, p)| {
-            let ni = i.checked_add_signed(*o)?;
-            let nj = j.checked_add_signed(*p)?;
-            if ni < self.width && nj < self.width {
-                Some(self[(ni, nj)])
-            } else {
-                None
-            }
-        })
-    }
+fn is_accessible(grid: &Grid<bool>, idx: (usize, usize)) -> bool {
+    grid[idx] && grid.neighbors8(idx).filter(|&n| grid[n]).count() < 4
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples;
+    use once_cell::sync::Lazy;
 
-    const EXAMPLE: &str = r"..@@.@@@@.
-@@@.@.@.@@
-@@@@@.@.@@
-@.@@@@..@.
-@@.@@@@.@@
-.@@@@@@@.@
-.@.@.@.@@@
-@.@@@.@@@@
-.@@@@@@@@.
-@.@.@@@.@.";
+    static EXAMPLE: Lazy<String> = Lazy::new(|| examples::input(2025, 4, 1));
 
     #[test]
     pub fn part1_example() {
-        assert_eq!(13, part1(EXAMPLE));
+        assert_eq!(13, part1(&EXAMPLE));
     }
 
     #[test]
     pub fn part2_example() {
-        assert_eq!(43, part2(EXAMPLE));
+        assert_eq!(43, part2(&EXAMPLE));
+    }
+
+    const WIDE: &str = "
+@@@@@
+@@@@@
+";
+
+    #[test]
+    pub fn non_square() {
+        assert_eq!(4, part1(WIDE));
+        assert_eq!(10, part2(WIDE));
+
+        let tall = parse_grid(WIDE).unwrap().transpose();
+        assert_eq!((2, 5), (tall.width(), tall.height()));
+        assert_eq!(4, count_accessible(&tall));
+        assert_eq!(10, remove_all_accessible(tall));
     }
 }
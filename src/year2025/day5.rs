@@ -3,25 +3,56 @@ use std::{
     ops::RangeInclusive,
 };
 
-pub fn main() {
-    let input = std::fs::read_to_string("input/2025/day5.txt").unwrap();
+use crate::{
+    provider,
+    solution::{Answer, Solution},
+    util::{ParseError, parse, scan},
+};
+
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed<'a> = Database;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1((ranges, items): &Self::Parsed<'_>) -> Answer {
+        count_fresh(ranges, items).into()
+    }
+
+    fn part2((ranges, _items): &Self::Parsed<'_>) -> Answer {
+        count_fresh_ids(ranges.clone()).into()
+    }
+}
+
+pub fn main() -> Result<(), provider::Error> {
+    let input = provider::input(2025, 5)?;
     dbg!(part1(&input));
     dbg!(part2(&input));
+    Ok(())
 }
 
 pub fn part1(input: &str) -> usize {
-    let (ranges, items) = parse(input);
-    let ranges = ranges.collect::<Vec<_>>();
+    let (ranges, items) = parse(input).unwrap();
+    count_fresh(&ranges, &items)
+}
 
+#[allow(dead_code, unused_variables)]
+pub fn part2(input: &str) -> usize {
+    let (ranges, _items) = parse(input).unwrap();
+    count_fresh_ids(ranges)
+}
+
+fn count_fresh(ranges: &[RangeInclusive<usize>], items: &[usize]) -> usize {
     items
+        .iter()
         .filter(|i| ranges.iter().any(|r| r.contains(i)))
         .count()
 }
 
-#[allow(dead_code, unused_variables)]
-pub fn part2(input: &str) -> usize {
-    let (ranges, _items) = parse(input);
-    let mut unmerged = ranges.collect::<Vec<_>>();
+fn count_fresh_ids(mut unmerged: Vec<RangeInclusive<usize>>) -> usize {
     let mut merged = Vec::<RangeInclusive<usize>>::with_capacity(unmerged.len());
 
     let mut prev_len;
@@ -51,49 +82,62 @@ pub fn part2(input: &str) -> usize {
         .sum::<usize>()
 }
 
-fn parse(
-    input: &str,
-) -> (
-    impl Iterator<Item = RangeInclusive<usize>>,
-    impl Iterator<Item = usize>,
-) {
-    let (raw_ranges, raw_items) = input.split_once("\n\n").unwrap();
+type Database = (Vec<RangeInclusive<usize>>, Vec<usize>);
 
-    let ranges = raw_ranges.split_ascii_whitespace().map(|raw_range| {
-        let (l, r) = raw_range.split_once("-").unwrap();
-        l.parse().unwrap()..=r.parse().unwrap()
-    });
+fn parse(input: &str) -> Result<Database, ParseError> {
+    let mut sections = parse::split_paragraphs(input);
+    let (Some(raw_ranges), Some(raw_items)) = (sections.next(), sections.next()) else {
+        return Err(ParseError::new(
+            input,
+            "fresh ranges and ingredient ids separated by a blank line",
+        ));
+    };
 
-    let items = raw_items
-        .split_ascii_whitespace()
-        .map(|raw_item| raw_item.parse().unwrap());
+    let ranges = parse::lines(raw_ranges, |raw_range| {
+        match scan::fields(raw_range, &["", "-", ""]) {
+            Some([l, r]) => Ok(l..=r),
+            None => Err(ParseError::new(raw_range, "a range like 3-5")),
+        }
+    })
+    .map_err(|e| e.within(input, raw_ranges))?;
+    let items = parse::lines(raw_items, |s| parse::number(s, "an ingredient id"))
+        .map_err(|e| e.within(input, raw_items))?;
 
-    (ranges, items)
+    Ok((ranges, items))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples;
+    use once_cell::sync::Lazy;
 
-    const EXAMPLE: &str = r"3-5
-10-14
-16-20
-12-18
-
-1
-5
-8
-11
-17
-32";
+    static EXAMPLE: Lazy<String> = Lazy::new(|| examples::input(2025, 5, 1));
 
     #[test]
     pub fn part1_example() {
-        assert_eq!(3, part1(EXAMPLE));
+        assert_eq!(3, part1(&EXAMPLE));
     }
 
     #[test]
     pub fn part2_example() {
-        assert_eq!(14, part2(EXAMPLE));
+        assert_eq!(14, part2(&EXAMPLE));
+    }
+
+    #[test]
+    pub fn malformed_ranges() {
+        let error = parse("3-5\n3-5x7\n\n1\n").unwrap_err();
+        assert_eq!(
+            (2, 1, "3-5x7"),
+            (error.line, error.column, error.text.as_str())
+        );
+        assert!(parse("3-5\n10-99999999999999999999\n\n1\n").is_err());
+    }
+
+    #[test]
+    pub fn crlf_input() {
+        let windows = EXAMPLE.replace('\n', "\r\n");
+        assert_eq!(3, part1(&windows));
+        assert_eq!(14, part2(&windows));
     }
 }
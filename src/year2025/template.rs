@@ -1,7 +1,32 @@
-pub fn main() {
-    let input = std::fs::read_to_string("input/2025/dayn.txt").unwrap();
+use crate::{
+    provider,
+    solution::{Answer, Solution},
+    util::ParseError,
+};
+
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Answer {
+        part2(input).into()
+    }
+}
+
+pub fn main() -> Result<(), provider::Error> {
+    let input = provider::input(2025, n)?;
     dbg!(part1(&input));
     // dbg!(part2(&input));
+    Ok(())
 }
 
 pub fn part1(input: &str) -> usize {
@@ -16,16 +41,18 @@ pub fn part2(input: &str) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples;
+    use once_cell::sync::Lazy;
 
-    const EXAMPLE: &str = r"";
+    static EXAMPLE: Lazy<String> = Lazy::new(|| examples::input(2025, n, 1));
 
     #[test]
     pub fn part1_example() {
-        assert_eq!(0, part1(EXAMPLE));
+        assert_eq!(0, part1(&EXAMPLE));
     }
 
     // #[test]
     // pub fn part2_example() {
-	// 	assert_eq!(0, part2(EXAMPLE));
+	// 	assert_eq!(0, part2(&EXAMPLE));
     // }
 }